@@ -0,0 +1,324 @@
+use chrono::NaiveDate;
+use seed::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::datetime::{DateField, PickerMsg};
+use crate::field::Field;
+use crate::form::{Form, FormViewOptions};
+use crate::input::{InputField, InputMsg};
+use crate::select::{SelectField, SelectMsg};
+use crate::slider::{SliderField, SliderMsg};
+use crate::summary::{view_summary, view_summary_row};
+use crate::textarea::TextAreaField;
+use crate::toggle::ToggleField;
+
+/// One of the components which can be added to a `FormBuilder`.
+pub enum AnyField {
+    Text(InputField<String>),
+    Number(InputField<f64>),
+    TextArea(TextAreaField<String>),
+    Toggle(ToggleField),
+    Select(SelectField),
+    Slider(SliderField),
+    Date(DateField),
+}
+
+pub enum AnyMsg {
+    Input(InputMsg),
+    Toggle,
+    Select(SelectMsg),
+    Slider(SliderMsg),
+    Date(PickerMsg),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AnyValue {
+    Text(String),
+    Number(f64),
+    Bool(bool),
+    Id(i64),
+    Date(NaiveDate),
+}
+
+macro_rules! any_field {
+    ($($variant:ident($ty:ty)),+) => {$(
+        impl From<$ty> for AnyField {
+            fn from(field: $ty) -> Self {
+                AnyField::$variant(field)
+            }
+        }
+    )+};
+}
+
+any_field!(
+    Text(InputField<String>),
+    Number(InputField<f64>),
+    TextArea(TextAreaField<String>),
+    Toggle(ToggleField),
+    Select(SelectField),
+    Slider(SliderField),
+    Date(DateField)
+);
+
+/// The raw state of an `AnyField`, by the kind of its input.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AnyState {
+    Text(String),
+    Bool(bool),
+    Id(Option<i64>),
+    Number(f64),
+}
+
+/// Calls the same method on the field, whichever component it is.
+macro_rules! each {
+    ($self:ident, $field:ident => $call:expr) => {
+        match $self {
+            AnyField::Text($field) => $call,
+            AnyField::Number($field) => $call,
+            AnyField::TextArea($field) => $call,
+            AnyField::Toggle($field) => $call,
+            AnyField::Select($field) => $call,
+            AnyField::Slider($field) => $call,
+            AnyField::Date($field) => $call,
+        }
+    };
+}
+
+impl Field for AnyField {
+    type Msg = AnyMsg;
+    type Value = Option<AnyValue>;
+    type State = AnyState;
+
+    fn update(&mut self, msg: Self::Msg, orders: &mut impl Orders<Self::Msg>) -> bool {
+        match (self, msg) {
+            (AnyField::Text(field), AnyMsg::Input(msg)) => {
+                field.update(msg, &mut orders.proxy(AnyMsg::Input))
+            }
+            (AnyField::Number(field), AnyMsg::Input(msg)) => {
+                field.update(msg, &mut orders.proxy(AnyMsg::Input))
+            }
+            (AnyField::TextArea(field), AnyMsg::Input(msg)) => {
+                field.update(msg, &mut orders.proxy(AnyMsg::Input))
+            }
+            (AnyField::Toggle(field), AnyMsg::Toggle) => {
+                field.update((), &mut orders.proxy(|_| AnyMsg::Toggle))
+            }
+            (AnyField::Select(field), AnyMsg::Select(msg)) => {
+                field.update(msg, &mut orders.proxy(AnyMsg::Select))
+            }
+            (AnyField::Slider(field), AnyMsg::Slider(msg)) => {
+                field.update(msg, &mut orders.proxy(AnyMsg::Slider))
+            }
+            (AnyField::Date(field), AnyMsg::Date(msg)) => {
+                field.update(msg, &mut orders.proxy(AnyMsg::Date))
+            }
+            _ => false,
+        }
+    }
+
+    fn reset(&mut self) {
+        each!(self, field => field.reset())
+    }
+
+    fn value(&self) -> Self::Value {
+        match self {
+            AnyField::Text(field) => field.value().map(AnyValue::Text),
+            AnyField::Number(field) => field.value().map(AnyValue::Number),
+            AnyField::TextArea(field) => field.value().map(AnyValue::Text),
+            AnyField::Toggle(field) => Some(AnyValue::Bool(field.value())),
+            AnyField::Select(field) => field.value().map(AnyValue::Id),
+            AnyField::Slider(field) => Some(AnyValue::Number(field.value())),
+            AnyField::Date(field) => field.value().map(AnyValue::Date),
+        }
+    }
+
+    /// Values of another kind than the field are ignored.
+    fn set_value(&mut self, value: Self::Value) {
+        match (self, value) {
+            (AnyField::Text(field), Some(AnyValue::Text(x))) => field.set_value(Some(x)),
+            (AnyField::Text(field), None) => field.set_value(None),
+            (AnyField::Number(field), Some(AnyValue::Number(x))) => field.set_value(Some(x)),
+            (AnyField::Number(field), None) => field.set_value(None),
+            (AnyField::TextArea(field), Some(AnyValue::Text(x))) => field.set_value(Some(x)),
+            (AnyField::TextArea(field), None) => field.set_value(None),
+            (AnyField::Toggle(field), Some(AnyValue::Bool(x))) => field.set_value(x),
+            (AnyField::Select(field), Some(AnyValue::Id(x))) => field.set_value(Some(x)),
+            (AnyField::Select(field), None) => field.set_value(None),
+            (AnyField::Slider(field), Some(AnyValue::Number(x))) => field.set_value(x),
+            (AnyField::Date(field), Some(AnyValue::Date(x))) => field.set_value(Some(x)),
+            (AnyField::Date(field), None) => field.set_value(None),
+            _ => {}
+        }
+    }
+
+    fn state(&self) -> Self::State {
+        match self {
+            AnyField::Text(field) => AnyState::Text(field.state()),
+            AnyField::Number(field) => AnyState::Text(field.state()),
+            AnyField::TextArea(field) => AnyState::Text(field.state()),
+            AnyField::Toggle(field) => AnyState::Bool(field.state()),
+            AnyField::Select(field) => AnyState::Id(field.state()),
+            AnyField::Slider(field) => AnyState::Number(field.state()),
+            AnyField::Date(field) => AnyState::Text(field.state()),
+        }
+    }
+
+    /// States of another kind than the field are ignored.
+    fn set_state(&mut self, state: Self::State) {
+        match (self, state) {
+            (AnyField::Text(field), AnyState::Text(x)) => field.set_state(x),
+            (AnyField::Number(field), AnyState::Text(x)) => field.set_state(x),
+            (AnyField::TextArea(field), AnyState::Text(x)) => field.set_state(x),
+            (AnyField::Toggle(field), AnyState::Bool(x)) => field.set_state(x),
+            (AnyField::Select(field), AnyState::Id(x)) => field.set_state(x),
+            (AnyField::Slider(field), AnyState::Number(x)) => field.set_state(x),
+            (AnyField::Date(field), AnyState::Text(x)) => field.set_state(x),
+            _ => {}
+        }
+    }
+
+    fn has_changed(&self) -> bool {
+        each!(self, field => field.has_changed())
+    }
+
+    fn view(&self, disabled: bool) -> Node<Self::Msg> {
+        match self {
+            AnyField::Text(field) => field.view(disabled).map_msg(AnyMsg::Input),
+            AnyField::Number(field) => field.view(disabled).map_msg(AnyMsg::Input),
+            AnyField::TextArea(field) => field.view(disabled).map_msg(AnyMsg::Input),
+            AnyField::Toggle(field) => field.view(disabled).map_msg(|_| AnyMsg::Toggle),
+            AnyField::Select(field) => field.view(disabled).map_msg(AnyMsg::Select),
+            AnyField::Slider(field) => field.view(disabled).map_msg(AnyMsg::Slider),
+            AnyField::Date(field) => field.view(disabled).map_msg(AnyMsg::Date),
+        }
+    }
+
+    fn label(&self) -> &str {
+        each!(self, field => field.label())
+    }
+
+    fn display_value(&self) -> String {
+        each!(self, field => field.display_value())
+    }
+
+    fn display_initial(&self) -> String {
+        each!(self, field => field.display_initial())
+    }
+
+    fn query(&self) -> Option<String> {
+        each!(self, field => field.query())
+    }
+
+    fn set_query(&mut self, query: &str) {
+        each!(self, field => field.set_query(query))
+    }
+}
+
+/// A form assembled at runtime from named fields, e.g. for confirmation dialogs.
+#[derive(Default)]
+pub struct FormBuilder {
+    fields: Vec<(String, AnyField)>,
+}
+
+impl FormBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn field(mut self, name: impl Into<String>, field: impl Into<AnyField>) -> Self {
+        self.fields.push((name.into(), field.into()));
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&AnyField> {
+        self.fields
+            .iter()
+            .find(|(x, _)| x == name)
+            .map(|(_, field)| field)
+    }
+}
+
+impl Form for FormBuilder {
+    type Msg = (usize, AnyMsg);
+    type Value = Option<BTreeMap<String, AnyValue>>;
+    type Snapshot = Vec<AnyState>;
+    type Patch = BTreeMap<String, Option<AnyValue>>;
+
+    fn update(&mut self, (index, msg): Self::Msg, orders: &mut impl Orders<Self::Msg>) -> bool {
+        match self.fields.get_mut(index) {
+            Some((_, field)) => field.update(msg, &mut orders.proxy(move |msg| (index, msg))),
+            None => false,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.fields.iter_mut().for_each(|(_, field)| field.reset());
+    }
+
+    fn value(&self) -> Self::Value {
+        self.fields
+            .iter()
+            .map(|(name, field)| Some((name.clone(), field.value()?)))
+            .collect()
+    }
+
+    fn snapshot(&self) -> Self::Snapshot {
+        self.fields.iter().map(|(_, field)| field.state()).collect()
+    }
+
+    fn restore(&mut self, snapshot: Self::Snapshot) {
+        for ((_, field), state) in self.fields.iter_mut().zip(snapshot) {
+            field.set_state(state);
+        }
+    }
+
+    /// The changed fields by name, with `None` for a value which was cleared or is invalid.
+    fn changes(&self) -> Self::Patch {
+        self.fields
+            .iter()
+            .filter(|(_, field)| field.has_changed())
+            .map(|(name, field)| (name.clone(), field.value()))
+            .collect()
+    }
+
+    fn view_with(&self, options: FormViewOptions) -> Vec<Node<Self::Msg>> {
+        self.fields
+            .iter()
+            .enumerate()
+            .map(|(index, (_, field))| field.view_with(options).map_msg(move |msg| (index, msg)))
+            .collect()
+    }
+
+    fn view_summary(&self) -> Node<Self::Msg> {
+        view_summary(
+            self.fields
+                .iter()
+                .map(|(_, field)| view_summary_row(field))
+                .collect(),
+        )
+    }
+
+    fn names(&self) -> Vec<String> {
+        self.fields.iter().map(|(name, _)| name.clone()).collect()
+    }
+
+    fn query(&self) -> Vec<(String, String)> {
+        self.fields
+            .iter()
+            .filter_map(|(name, field)| Some((name.clone(), field.query()?)))
+            .collect()
+    }
+
+    fn restore_query(&mut self, search: &UrlSearch) {
+        for (name, field) in &mut self.fields {
+            if let Some(query) = search.get(name).and_then(|values| values.first()) {
+                field.set_query(query);
+            }
+        }
+    }
+
+    fn has_changed(&self) -> bool {
+        self.fields.iter().any(|(_, field)| field.has_changed())
+    }
+}
@@ -0,0 +1,305 @@
+use seed::{prelude::*, *};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::field::{readonly, Field};
+use crate::input::{error, InputMsg};
+
+pub static INVALID_COLOR: &str = "Must be a hex (#3273dc) or rgb (rgb(50, 115, 220)) color.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+}
+
+/// Formats as `#rrggbb`, which can be used directly as a css color.
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+impl FromStr for Color {
+    type Err = String;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let str = str.trim().to_lowercase();
+
+        if let Some(inner) = str.strip_prefix("rgb(").and_then(|x| x.strip_suffix(')')) {
+            let parts: Result<Vec<u8>, _> = inner.split(',').map(|x| x.trim().parse()).collect();
+            return match parts.as_deref() {
+                Ok([r, g, b]) => Ok(Color::new(*r, *g, *b)),
+                _ => Err(INVALID_COLOR.to_owned()),
+            };
+        }
+
+        let hex = str.strip_prefix('#').unwrap_or(&str);
+        let digits: Option<Vec<u8>> = hex
+            .chars()
+            .map(|x| x.to_digit(16).map(|x| x as u8))
+            .collect();
+
+        match digits.as_deref() {
+            Some([r, g, b]) => Ok(Color::new(r * 17, g * 17, b * 17)),
+            Some([r1, r2, g1, g2, b1, b2]) => {
+                Ok(Color::new(r1 * 16 + r2, g1 * 16 + g2, b1 * 16 + b2))
+            }
+            _ => Err(INVALID_COLOR.to_owned()),
+        }
+    }
+}
+
+pub struct ColorField {
+    label: String,
+
+    value: String,
+    initial: Option<Color>,
+
+    palette: Option<Vec<Color>>,
+
+    untouched: bool,
+    optional: bool,
+}
+
+impl ColorField {
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            value: String::new(),
+            initial: None,
+            palette: None,
+            untouched: true,
+            optional: false,
+        }
+    }
+
+    pub fn initial(mut self, initial: Color) -> Self {
+        self.value = initial.to_string();
+        self.initial = Some(initial);
+        self
+    }
+
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+
+    /// Only allows picking one of the given colors, shown as swatches.
+    pub fn palette(mut self, palette: Vec<Color>) -> Self {
+        self.palette = Some(palette);
+        self
+    }
+
+    fn view_swatch(&self, color: Color, disabled: bool) -> Node<InputMsg> {
+        let selected = self.value() == Some(color);
+        button![
+            C!["button", IF!(selected => "is-dark")],
+            style! {St::Background => color.to_string(), St::Width => "2.5em"},
+            attrs! {At::Type => "button", At::Title => color.to_string()},
+            IF!(disabled => attrs! {At::Disabled => ""}),
+            ev(Ev::Click, move |_| InputMsg::Value(color.to_string())),
+            IF!(selected => span![C!["icon", "has-text-white"], i![C!["fas", "fa-check"]]]),
+        ]
+    }
+
+    fn view_palette(&self, palette: &[Color], disabled: bool) -> Node<InputMsg> {
+        div![
+            C!["buttons", "mb-2"],
+            palette
+                .iter()
+                .map(|&color| self.view_swatch(color, disabled))
+        ]
+    }
+
+    fn view_inputs(&self, danger: bool, disabled: bool) -> Node<InputMsg> {
+        let current = self.value().unwrap_or_default().to_string();
+
+        div![
+            C!["field", "has-addons", IF!(!danger => "mb-2")],
+            div![
+                C!["control"],
+                span![
+                    C!["button", "is-static"],
+                    style! {St::Background => &current, St::Width => "2.5em"}
+                ]
+            ],
+            div![
+                C!["control", "is-expanded"],
+                input![
+                    C!["input", IF!(danger => "is-danger")],
+                    input_ev(Ev::Input, InputMsg::Value),
+                    ev(Ev::Blur, |_| InputMsg::Blur),
+                    attrs! {At::Value => &self.value, At::Placeholder => "#3273dc"},
+                    IF!(disabled => attrs! {At::Disabled => ""}),
+                ]
+            ],
+            div![
+                C!["control"],
+                input![
+                    C!["input"],
+                    style! {St::Width => "3.5em", St::Padding => "2px"},
+                    attrs! {At::Type => "color", At::Value => current},
+                    input_ev(Ev::Input, InputMsg::Value),
+                    IF!(disabled => attrs! {At::Disabled => ""}),
+                ]
+            ],
+        ]
+    }
+}
+
+impl Field for ColorField {
+    type Msg = InputMsg;
+    type Value = Option<Color>;
+    type State = String;
+
+    fn update(&mut self, msg: Self::Msg, _: &mut impl Orders<Self::Msg>) -> bool {
+        self.untouched = false;
+
+        self.value = match msg {
+            InputMsg::Value(str) => str,
+            InputMsg::Blur | InputMsg::Submit => return false,
+        };
+        true
+    }
+
+    fn reset(&mut self) {
+        self.value = match &self.initial {
+            Some(x) => x.to_string(),
+            None => String::new(),
+        }
+    }
+
+    fn value(&self) -> Self::Value {
+        self.value.parse().ok()
+    }
+
+    fn set_value(&mut self, value: Self::Value) {
+        self.value = value.map(|x| x.to_string()).unwrap_or_default();
+    }
+
+    fn state(&self) -> Self::State {
+        self.value.clone()
+    }
+
+    fn set_state(&mut self, state: Self::State) {
+        self.value = state;
+    }
+
+    fn has_changed(&self) -> bool {
+        self.initial != self.value()
+    }
+
+    fn view(&self, disabled: bool) -> Node<Self::Msg> {
+        let error = error(&Color::from_str, &self.value, self.untouched, self.optional);
+        let danger = error.is_some();
+
+        div![
+            C!["field"],
+            label![
+                C!["label"],
+                &self.label,
+                IF!(self.optional => i![C!["has-text-grey"], " - Optional"])
+            ],
+            match &self.palette {
+                Some(palette) => self.view_palette(palette, disabled),
+                None => self.view_inputs(danger, disabled),
+            },
+            p![C!["help", "is-danger"], error.unwrap_or_default()],
+        ]
+    }
+
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn display_value(&self) -> String {
+        self.value().map(|x| x.to_string()).unwrap_or_default()
+    }
+
+    fn display_initial(&self) -> String {
+        self.initial.map(|x| x.to_string()).unwrap_or_default()
+    }
+
+    /// The hex form, e.g. `#3273dc`.
+    fn query(&self) -> Option<String> {
+        self.value().map(|x| x.to_string())
+    }
+
+    fn set_query(&mut self, query: &str) {
+        if let Ok(color) = query.parse::<Color>() {
+            self.set_value(Some(color));
+        }
+    }
+
+    fn view_readonly(&self) -> Node<Self::Msg> {
+        let color = match self.value() {
+            Some(color) => color,
+            None => return readonly(&self.label, ""),
+        };
+        div![
+            C!["field"],
+            label![C!["label"], &self.label],
+            p![
+                C!["mb-2"],
+                span![
+                    C!["tag", "mr-2"],
+                    style! {St::Background => color.to_string(), St::Width => "2.5em"}
+                ],
+                color.to_string()
+            ]
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_hex() {
+        assert_eq!("#3273dc".parse(), Ok(Color::new(0x32, 0x73, 0xdc)));
+        assert_eq!("3273DC".parse(), Ok(Color::new(0x32, 0x73, 0xdc)));
+        assert_eq!(" #fa0 ".parse(), Ok(Color::new(0xff, 0xaa, 0x00)));
+    }
+
+    #[test]
+    fn parses_rgb() {
+        assert_eq!("rgb(50, 115, 220)".parse(), Ok(Color::new(50, 115, 220)));
+        assert_eq!("RGB(0,0,0)".parse(), Ok(Color::new(0, 0, 0)));
+    }
+
+    #[test]
+    fn rejects_invalid_colors() {
+        for str in &[
+            "",
+            "#12345",
+            "#ggg",
+            "rgb(256, 0, 0)",
+            "rgb(1, 2)",
+            "rgb(1, 2, 3, 4)",
+        ] {
+            assert_eq!(
+                str.parse::<Color>(),
+                Err(INVALID_COLOR.to_owned()),
+                "{}",
+                str
+            );
+        }
+    }
+
+    #[test]
+    fn round_trips() {
+        let color = Color::new(1, 128, 255);
+        assert_eq!(color.to_string(), "#0180ff");
+        assert_eq!(color.to_string().parse(), Ok(color));
+    }
+}
@@ -0,0 +1,552 @@
+use seed::{prelude::*, *};
+
+use crate::button::Button;
+use crate::field::Field;
+use crate::toggle::ToggleField;
+use crate::tuple::RequiredField;
+
+pub enum PairMsg<A, B> {
+    First(A),
+    Second(B),
+}
+
+/// Two fields shown below each other, with both values combined.
+pub struct PairField<A: Field, B: Field> {
+    first: A,
+    second: B,
+}
+
+impl<A: Field, B: Field> PairField<A, B> {
+    pub fn new(first: A, second: B) -> Self {
+        Self { first, second }
+    }
+}
+
+impl<A: Field, B: Field> Field for PairField<A, B> {
+    type Msg = PairMsg<A::Msg, B::Msg>;
+    type Value = (A::Value, B::Value);
+    type State = (A::State, B::State);
+
+    fn update(&mut self, msg: Self::Msg, orders: &mut impl Orders<Self::Msg>) -> bool {
+        match msg {
+            PairMsg::First(msg) => self.first.update(msg, &mut orders.proxy(PairMsg::First)),
+            PairMsg::Second(msg) => self.second.update(msg, &mut orders.proxy(PairMsg::Second)),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.first.reset();
+        self.second.reset();
+    }
+
+    fn value(&self) -> Self::Value {
+        (self.first.value(), self.second.value())
+    }
+
+    fn set_value(&mut self, (first, second): Self::Value) {
+        self.first.set_value(first);
+        self.second.set_value(second);
+    }
+
+    fn state(&self) -> Self::State {
+        (self.first.state(), self.second.state())
+    }
+
+    fn set_state(&mut self, (first, second): Self::State) {
+        self.first.set_state(first);
+        self.second.set_state(second);
+    }
+
+    fn has_changed(&self) -> bool {
+        self.first.has_changed() || self.second.has_changed()
+    }
+
+    fn view(&self, disabled: bool) -> Node<Self::Msg> {
+        div![
+            self.first.view(disabled).map_msg(PairMsg::First),
+            self.second.view(disabled).map_msg(PairMsg::Second),
+        ]
+    }
+
+    fn label(&self) -> &str {
+        ""
+    }
+
+    fn display_value(&self) -> String {
+        format!(
+            "{}, {}",
+            self.first.display_value(),
+            self.second.display_value()
+        )
+    }
+
+    fn display_initial(&self) -> String {
+        format!(
+            "{}, {}",
+            self.first.display_initial(),
+            self.second.display_initial()
+        )
+    }
+
+    fn view_readonly(&self) -> Node<Self::Msg> {
+        div![
+            self.first.view_readonly().map_msg(PairMsg::First),
+            self.second.view_readonly().map_msg(PairMsg::Second),
+        ]
+    }
+}
+
+impl<A: RequiredField, B: RequiredField> RequiredField for PairField<A, B> {
+    type Inner = (A::Inner, B::Inner);
+
+    fn required_value(&self) -> Option<Self::Inner> {
+        Some((self.first.required_value()?, self.second.required_value()?))
+    }
+}
+
+pub enum OptionalMsg<M> {
+    Toggle,
+    Field(M),
+}
+
+/// A field which is only shown and used after switching on its toggle.
+pub struct OptionalField<F: Field> {
+    label: String,
+    toggle: ToggleField,
+    field: F,
+}
+
+impl<F: Field> OptionalField<F> {
+    pub fn new(label: impl Into<String>, field: F) -> Self {
+        let label = label.into();
+        Self {
+            toggle: ToggleField::new(&label, false),
+            label,
+            field,
+        }
+    }
+
+    /// Switches the toggle on initially.
+    pub fn enabled(mut self) -> Self {
+        self.toggle = ToggleField::new(&self.label, true);
+        self
+    }
+}
+
+impl<F: Field> Field for OptionalField<F> {
+    type Msg = OptionalMsg<F::Msg>;
+    type Value = Option<F::Value>;
+    type State = (bool, F::State);
+
+    fn update(&mut self, msg: Self::Msg, orders: &mut impl Orders<Self::Msg>) -> bool {
+        match msg {
+            OptionalMsg::Toggle => self
+                .toggle
+                .update((), &mut orders.proxy(|_| OptionalMsg::Toggle)),
+            OptionalMsg::Field(msg) => self
+                .field
+                .update(msg, &mut orders.proxy(OptionalMsg::Field)),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.toggle.reset();
+        self.field.reset();
+    }
+
+    fn value(&self) -> Self::Value {
+        match self.toggle.value() {
+            true => Some(self.field.value()),
+            false => None,
+        }
+    }
+
+    fn set_value(&mut self, value: Self::Value) {
+        self.toggle.set_value(value.is_some());
+        if let Some(value) = value {
+            self.field.set_value(value);
+        }
+    }
+
+    fn state(&self) -> Self::State {
+        (self.toggle.value(), self.field.state())
+    }
+
+    fn set_state(&mut self, (enabled, state): Self::State) {
+        self.toggle.set_value(enabled);
+        self.field.set_state(state);
+    }
+
+    fn has_changed(&self) -> bool {
+        self.toggle.has_changed() || (self.toggle.value() && self.field.has_changed())
+    }
+
+    fn view(&self, disabled: bool) -> Node<Self::Msg> {
+        div![
+            self.toggle.view(disabled).map_msg(|_| OptionalMsg::Toggle),
+            IF!(self.toggle.value() => self.field.view(disabled).map_msg(OptionalMsg::Field)),
+        ]
+    }
+
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn display_value(&self) -> String {
+        match self.toggle.value() {
+            true => self.field.display_value(),
+            false => String::new(),
+        }
+    }
+
+    fn display_initial(&self) -> String {
+        // The toggle has changed exactly when its value differs from the initial one
+        match self.toggle.value() != self.toggle.has_changed() {
+            true => self.field.display_initial(),
+            false => String::new(),
+        }
+    }
+
+    /// The query of the field while the toggle is on.
+    fn query(&self) -> Option<String> {
+        self.toggle.value().then(|| self.field.query()).flatten()
+    }
+
+    fn set_query(&mut self, query: &str) {
+        self.field.set_query(query);
+        if self.field.query().is_some() {
+            self.toggle.set_value(true);
+        }
+    }
+}
+
+// Switching the field off is a proper value, only an enabled field can be missing
+impl<F: RequiredField> RequiredField for OptionalField<F> {
+    type Inner = Option<F::Inner>;
+
+    fn required_value(&self) -> Option<Self::Inner> {
+        match self.toggle.value() {
+            true => self.field.required_value().map(Some),
+            false => Some(None),
+        }
+    }
+}
+
+pub enum ListMsg<M> {
+    Item(usize, M),
+    Add,
+    Remove(usize),
+}
+
+/// A growable list of fields, new items are created by the given function.
+pub struct ListField<F: Field> {
+    label: String,
+
+    items: Vec<F>,
+    /// The states of the initial items, the list has changed when its state differs.
+    initial_states: Vec<F::State>,
+
+    create: Box<dyn Fn() -> F>,
+    initial: Box<dyn Fn() -> Vec<F>>,
+}
+
+impl<F: Field + 'static> ListField<F> {
+    pub fn new(label: impl Into<String>, create: impl Fn() -> F + 'static) -> Self {
+        Self {
+            label: label.into(),
+            items: Vec::new(),
+            initial_states: Vec::new(),
+            create: Box::new(create),
+            initial: Box::new(Vec::new),
+        }
+    }
+
+    /// Builds the initial items, this is called again when resetting.
+    pub fn initial(mut self, initial: impl Fn() -> Vec<F> + 'static) -> Self {
+        self.initial = Box::new(initial);
+        self.reset();
+        self
+    }
+}
+
+impl<F: Field> Field for ListField<F> {
+    type Msg = ListMsg<F::Msg>;
+    type Value = Vec<F::Value>;
+    type State = Vec<F::State>;
+
+    fn update(&mut self, msg: Self::Msg, orders: &mut impl Orders<Self::Msg>) -> bool {
+        match msg {
+            ListMsg::Item(index, msg) if index < self.items.len() => self.items[index]
+                .update(msg, &mut orders.proxy(move |msg| ListMsg::Item(index, msg))),
+            ListMsg::Add => {
+                self.items.push((self.create)());
+                true
+            }
+            ListMsg::Remove(index) if index < self.items.len() => {
+                self.items.remove(index);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.items = (self.initial)();
+        self.initial_states = self.state();
+    }
+
+    fn value(&self) -> Self::Value {
+        self.items.iter().map(Field::value).collect()
+    }
+
+    fn set_value(&mut self, value: Self::Value) {
+        self.items.truncate(value.len());
+        while self.items.len() < value.len() {
+            self.items.push((self.create)());
+        }
+        for (item, value) in self.items.iter_mut().zip(value) {
+            item.set_value(value);
+        }
+    }
+
+    fn state(&self) -> Self::State {
+        self.items.iter().map(Field::state).collect()
+    }
+
+    fn set_state(&mut self, state: Self::State) {
+        self.items.truncate(state.len());
+        while self.items.len() < state.len() {
+            self.items.push((self.create)());
+        }
+        for (item, state) in self.items.iter_mut().zip(state) {
+            item.set_state(state);
+        }
+    }
+
+    fn has_changed(&self) -> bool {
+        self.state() != self.initial_states
+    }
+
+    fn view(&self, disabled: bool) -> Node<Self::Msg> {
+        div![
+            C!["field"],
+            label![C!["label"], &self.label],
+            self.items.iter().enumerate().map(|(index, item)| {
+                div![
+                    C!["is-flex", "is-align-items-flex-start"],
+                    div![
+                        C!["is-flex-grow-1", "mr-2"],
+                        item.view(disabled)
+                            .map_msg(move |msg| ListMsg::Item(index, msg))
+                    ],
+                    Button::trash(move || ListMsg::Remove(index)).disabled(disabled),
+                ]
+            }),
+            Button::create(|| ListMsg::Add).disabled(disabled),
+        ]
+    }
+
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn display_value(&self) -> String {
+        let values: Vec<_> = self.items.iter().map(Field::display_value).collect();
+        values.join("\n")
+    }
+
+    fn display_initial(&self) -> String {
+        let values: Vec<_> = (self.initial)()
+            .iter()
+            .map(Field::display_initial)
+            .collect();
+        values.join("\n")
+    }
+
+    fn view_readonly(&self) -> Node<Self::Msg> {
+        div![
+            C!["field"],
+            label![C!["label"], &self.label],
+            self.items.iter().enumerate().map(|(index, item)| {
+                item.view_readonly()
+                    .map_msg(move |msg| ListMsg::Item(index, msg))
+            }),
+            IF!(self.items.is_empty() => p![C!["mb-2", "has-text-grey"], "—"]),
+        ]
+    }
+}
+
+/// Converts the value of a field through a fallible function, showing its error below the field.
+pub struct MappedField<F: Field, B> {
+    field: F,
+    map: Box<dyn Fn(F::Value) -> Result<B, String>>,
+    inverse: Option<Box<dyn Fn(B) -> F::Value>>,
+    untouched: bool,
+}
+
+impl<F: Field, B> MappedField<F, B> {
+    pub fn new(field: F, map: impl Fn(F::Value) -> Result<B, String> + 'static) -> Self {
+        Self {
+            field,
+            map: Box::new(map),
+            inverse: None,
+            untouched: true,
+        }
+    }
+
+    /// Converts values back, which allows `set_value` to update the inner field.
+    pub fn inverse(mut self, inverse: impl Fn(B) -> F::Value + 'static) -> Self {
+        self.inverse = Some(Box::new(inverse));
+        self
+    }
+}
+
+impl<F: Field, B> Field for MappedField<F, B> {
+    type Msg = F::Msg;
+    type Value = Option<B>;
+    type State = F::State;
+
+    fn update(&mut self, msg: Self::Msg, orders: &mut impl Orders<Self::Msg>) -> bool {
+        self.untouched = false;
+        self.field.update(msg, orders)
+    }
+
+    fn reset(&mut self) {
+        self.field.reset();
+        self.untouched = true;
+    }
+
+    fn value(&self) -> Self::Value {
+        (self.map)(self.field.value()).ok()
+    }
+
+    /// Only has an effect when an inverse function is given.
+    fn set_value(&mut self, value: Self::Value) {
+        if let (Some(inverse), Some(value)) = (&self.inverse, value) {
+            self.field.set_value(inverse(value));
+        }
+    }
+
+    /// The state of the inner field, so no inverse is needed to restore it.
+    fn state(&self) -> Self::State {
+        self.field.state()
+    }
+
+    fn set_state(&mut self, state: Self::State) {
+        self.field.set_state(state);
+    }
+
+    fn has_changed(&self) -> bool {
+        self.field.has_changed()
+    }
+
+    fn view(&self, disabled: bool) -> Node<Self::Msg> {
+        let error = match self.untouched {
+            true => None,
+            false => (self.map)(self.field.value()).err(),
+        };
+
+        div![
+            self.field.view(disabled),
+            error.map(|err| p![C!["help", "is-danger", "mt-0", "mb-2"], err]),
+        ]
+    }
+
+    fn label(&self) -> &str {
+        self.field.label()
+    }
+
+    fn display_value(&self) -> String {
+        self.field.display_value()
+    }
+
+    fn display_initial(&self) -> String {
+        self.field.display_initial()
+    }
+
+    fn view_readonly(&self) -> Node<Self::Msg> {
+        self.field.view_readonly()
+    }
+
+    /// The query of the inner field, only while the value can be converted.
+    fn query(&self) -> Option<String> {
+        self.value().and_then(|_| self.field.query())
+    }
+
+    fn set_query(&mut self, query: &str) {
+        let state = self.field.state();
+        self.field.set_query(query);
+        if self.value().is_none() {
+            self.field.set_state(state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::InputField;
+
+    fn number() -> InputField<i64> {
+        InputField::new("count", |x| {
+            x.parse().map_err(|_| "Must be a number.".to_owned())
+        })
+    }
+
+    #[test]
+    fn states_keep_invalid_input() {
+        let mut field = number();
+        field.set_state("12a".to_owned());
+        assert_eq!(field.value(), None);
+        assert_eq!(field.state(), "12a");
+    }
+
+    #[test]
+    fn mapped_fields_restore_without_inverse() {
+        let mut field = MappedField::new(number(), |x| x.ok_or_else(|| "Missing.".to_owned()));
+        field.set_value(Some(3));
+        assert_eq!(field.value(), None);
+
+        field.set_state("3".to_owned());
+        assert_eq!(field.value(), Some(3));
+    }
+
+    #[test]
+    fn mapped_queries_are_only_written_when_valid() {
+        let positive = |x: Option<i64>| x.filter(|&x| x > 0).ok_or_else(|| "Too small.".to_owned());
+        let mut field = MappedField::new(number(), positive);
+        field.set_query("-3");
+        assert_eq!(field.query(), None);
+        assert_eq!(field.state(), "");
+
+        field.set_query("3");
+        assert_eq!(field.query().as_deref(), Some("3"));
+    }
+
+    #[test]
+    fn lists_restore_their_items() {
+        let mut list = ListField::new("counts", number);
+        list.set_state(vec!["1".to_owned(), "x".to_owned()]);
+        assert_eq!(list.value(), vec![Some(1), None]);
+        assert_eq!(list.state(), ["1", "x"]);
+    }
+
+    #[test]
+    fn lists_notice_removed_and_added_items() {
+        let mut list = ListField::new("counts", number).initial(|| {
+            let mut items = vec![number(), number()];
+            items[0].set_value(Some(1));
+            items[1].set_value(Some(2));
+            items
+        });
+        assert!(!list.has_changed());
+
+        list.items.remove(0);
+        list.items.push(number());
+        assert!(list.has_changed());
+
+        list.items[0].set_state("1".to_owned());
+        list.items[1].set_state("2".to_owned());
+        assert!(!list.has_changed());
+    }
+}
@@ -0,0 +1,183 @@
+use seed::{prelude::*, *};
+use std::collections::BTreeMap;
+
+use crate::button::Button;
+use crate::form::{Form, FormViewOptions};
+use crate::tuple::Required;
+
+/// Errors returned by the backend, e.g. from a 422 response, keyed by field name.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FormErrors {
+    pub message: Option<String>,
+    pub fields: BTreeMap<String, String>,
+}
+
+impl FormErrors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    pub fn field(mut self, name: impl Into<String>, error: impl Into<String>) -> Self {
+        self.fields.insert(name.into(), error.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormState {
+    Editing,
+    Submitting,
+    Submitted,
+    Failed(FormErrors),
+}
+
+pub enum ControllerMsg<M> {
+    Form(M),
+    /// A message from the view of the named field, which clears its error from the server.
+    Field(String, M),
+    Submit,
+    Cancel,
+}
+
+/// Wraps a form with submit and cancel buttons and tracks the state of its submission.
+///
+/// The parent handles `ControllerMsg::Submit` by calling `submit`, sending the value
+/// and reporting the outcome with `submitted` or `failed`.
+pub struct FormController<F: Form> {
+    form: F,
+    state: FormState,
+
+    submit: Button<ControllerMsg<F::Msg>>,
+    cancel: Button<ControllerMsg<F::Msg>>,
+}
+
+impl<F: Form> FormController<F>
+where
+    F::Value: Required,
+{
+    pub fn new(form: F) -> Self {
+        Self {
+            form,
+            state: FormState::Editing,
+            submit: Button::confirm(|| ControllerMsg::Submit),
+            cancel: Button::delete(|| ControllerMsg::Cancel),
+        }
+    }
+
+    pub fn form(&self) -> &F {
+        &self.form
+    }
+
+    pub fn state(&self) -> &FormState {
+        &self.state
+    }
+
+    pub fn submitting(&self) -> bool {
+        self.state == FormState::Submitting
+    }
+
+    /// Starts submitting, returning the value to send if the form is valid and idle.
+    pub fn submit(&mut self) -> Option<<F::Value as Required>::Inner> {
+        if self.submitting() {
+            return None;
+        }
+        let value = self.form.value().required()?;
+        self.state = FormState::Submitting;
+        Some(value)
+    }
+
+    pub fn submitted(&mut self) {
+        self.state = FormState::Submitted;
+    }
+
+    pub fn failed(&mut self, errors: FormErrors) {
+        self.state = FormState::Failed(errors);
+    }
+
+    pub fn update(
+        &mut self,
+        msg: ControllerMsg<F::Msg>,
+        orders: &mut impl Orders<ControllerMsg<F::Msg>>,
+    ) -> bool {
+        match msg {
+            ControllerMsg::Form(_) if self.submitting() => false,
+            ControllerMsg::Form(msg) => {
+                if self.state == FormState::Submitted {
+                    self.state = FormState::Editing;
+                }
+                self.form
+                    .update(msg, &mut orders.proxy(ControllerMsg::Form))
+            }
+            ControllerMsg::Field(name, msg) => {
+                if let FormState::Failed(errors) = &mut self.state {
+                    errors.fields.remove(&name);
+                }
+                self.update(ControllerMsg::Form(msg), orders)
+            }
+            ControllerMsg::Cancel if self.submitting() => false,
+            ControllerMsg::Cancel => {
+                self.form.reset();
+                self.state = FormState::Editing;
+                true
+            }
+            ControllerMsg::Submit => false,
+        }
+    }
+
+    pub fn view(&self) -> Node<ControllerMsg<F::Msg>> {
+        let submitting = self.submitting();
+        let names = self.form.names();
+
+        let empty = BTreeMap::new();
+        let (message, errors) = match &self.state {
+            FormState::Failed(errors) => (errors.message.as_ref(), &errors.fields),
+            _ => (None, &empty),
+        };
+
+        // Errors of unknown fields are shown on top, as they can't be placed below a field
+        let unknown = errors
+            .iter()
+            .filter(|(name, _)| !names.contains(name))
+            .map(|(name, err)| format!("{}: {}", name, err));
+        let messages: Vec<_> = message.cloned().into_iter().chain(unknown).collect();
+
+        let fields = self
+            .form
+            .view_with(FormViewOptions {
+                disabled: submitting,
+                readonly: false,
+            })
+            .into_iter()
+            .enumerate()
+            .map(|(index, node)| {
+                let error = names.get(index).and_then(|name| errors.get(name));
+                let name = names.get(index).cloned();
+                div![
+                    node.map_msg(move |msg| match name {
+                        Some(name) => ControllerMsg::Field(name, msg),
+                        None => ControllerMsg::Form(msg),
+                    }),
+                    error.map(|err| p![C!["help", "is-danger", "mt-0", "mb-2"], err]),
+                ]
+            });
+
+        div![
+            IF!(!messages.is_empty() => div![
+                C!["notification", "is-danger", "is-light"],
+                messages.iter().map(|message| p![message])
+            ]),
+            fields,
+            div![
+                C!["buttons"],
+                self.submit
+                    .disabled(submitting || self.form.value().required().is_none()),
+                self.cancel.disabled(submitting),
+            ]
+        ]
+    }
+}
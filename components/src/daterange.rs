@@ -0,0 +1,326 @@
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use seed::{prelude::*, *};
+
+use crate::datetime::{DateField, PickerMsg};
+use crate::field::Field;
+
+pub static INVERTED: &str = "The start date must be on or before the end date.";
+pub static INCOMPLETE: &str = "Fill in both dates or neither.";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Preset {
+    Today,
+    LastDays(u32),
+    ThisWeek,
+    ThisMonth,
+    LastMonth,
+    ThisYear,
+    Custom,
+}
+
+impl Preset {
+    pub fn name(&self) -> String {
+        match self {
+            Preset::Today => "Today".to_owned(),
+            Preset::LastDays(days) => format!("Last {} days", days),
+            Preset::ThisWeek => "This week".to_owned(),
+            Preset::ThisMonth => "This month".to_owned(),
+            Preset::LastMonth => "Last month".to_owned(),
+            Preset::ThisYear => "This year".to_owned(),
+            Preset::Custom => "Custom".to_owned(),
+        }
+    }
+
+    /// The range this preset covers relative to `today`, custom has no fixed range.
+    /// The last 0 days are treated as today, so the range is never inverted.
+    pub fn range(&self, today: NaiveDate) -> Option<(NaiveDate, NaiveDate)> {
+        let month = today.with_day(1)?;
+        match self {
+            Preset::Today => Some((today, today)),
+            Preset::LastDays(days) => {
+                let start = today - Duration::days(days.saturating_sub(1) as i64);
+                Some((start, today))
+            }
+            Preset::ThisWeek => {
+                let start = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+                Some((start, today))
+            }
+            Preset::ThisMonth => Some((month, today)),
+            Preset::LastMonth => {
+                let end = month.pred_opt()?;
+                Some((end.with_day(1)?, end))
+            }
+            Preset::ThisYear => Some((today.with_ordinal(1)?, today)),
+            Preset::Custom => None,
+        }
+    }
+}
+
+fn text(start: String, end: String) -> String {
+    match start.is_empty() && end.is_empty() {
+        true => String::new(),
+        false => format!("{} – {}", start, end),
+    }
+}
+
+pub enum DateRangeMsg {
+    /// The index of the selected preset, `None` for custom.
+    Preset(Option<usize>),
+    Start(PickerMsg),
+    End(PickerMsg),
+}
+
+pub struct DateRangeField {
+    label: String,
+
+    start: DateField,
+    end: DateField,
+
+    presets: Vec<Preset>,
+    preset: Option<usize>,
+
+    optional: bool,
+}
+
+impl DateRangeField {
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            start: DateField::new("From"),
+            end: DateField::new("To"),
+            presets: vec![
+                Preset::LastDays(7),
+                Preset::LastDays(30),
+                Preset::ThisMonth,
+                Preset::LastMonth,
+                Preset::ThisYear,
+            ],
+            preset: None,
+            optional: false,
+        }
+    }
+
+    pub fn initial(mut self, start: NaiveDate, end: NaiveDate) -> Self {
+        self.start = self.start.initial(start);
+        self.end = self.end.initial(end);
+        self
+    }
+
+    /// Allows leaving both dates empty, e.g. for a filter which isn't applied.
+    pub fn optional(mut self) -> Self {
+        self.start = self.start.optional();
+        self.end = self.end.optional();
+        self.optional = true;
+        self
+    }
+
+    pub fn presets(mut self, presets: Vec<Preset>) -> Self {
+        self.presets = presets;
+        self
+    }
+
+    pub fn min(mut self, min: NaiveDate) -> Self {
+        self.start = self.start.min(min);
+        self.end = self.end.min(min);
+        self
+    }
+
+    pub fn max(mut self, max: NaiveDate) -> Self {
+        self.start = self.start.max(max);
+        self.end = self.end.max(max);
+        self
+    }
+
+    fn error(&self) -> Option<&'static str> {
+        match (self.start.value(), self.end.value()) {
+            (Some(start), Some(end)) if start > end => Some(INVERTED),
+            (Some(_), None) | (None, Some(_)) if self.optional => Some(INCOMPLETE),
+            _ => None,
+        }
+    }
+
+    fn view_presets(&self, disabled: bool) -> Node<DateRangeMsg> {
+        let option = |(index, preset): (usize, &Preset)| {
+            let selected = self.preset == Some(index);
+            option![
+                attrs! {At::Value => index, At::Selected => selected.as_at_value()},
+                preset.name()
+            ]
+        };
+
+        div![
+            C!["select"],
+            select![
+                IF!(disabled => attrs! {At::Disabled => ""}),
+                input_ev(Ev::Input, |str| DateRangeMsg::Preset(str.parse().ok())),
+                option![
+                    attrs! {At::Value => "", At::Selected => self.preset.is_none().as_at_value()},
+                    Preset::Custom.name()
+                ],
+                self.presets.iter().enumerate().map(option)
+            ]
+        ]
+    }
+}
+
+impl Field for DateRangeField {
+    type Msg = DateRangeMsg;
+    type Value = Option<(NaiveDate, NaiveDate)>;
+    type State = (Option<usize>, String, String);
+
+    fn update(&mut self, msg: Self::Msg, orders: &mut impl Orders<Self::Msg>) -> bool {
+        match msg {
+            DateRangeMsg::Preset(index) => {
+                let today = Local::now().date_naive();
+                let range = index
+                    .and_then(|index| self.presets.get(index))
+                    .and_then(|x| x.range(today));
+                self.preset = range.and(index);
+
+                if let Some((start, end)) = range {
+                    self.start.set_value(Some(start));
+                    self.end.set_value(Some(end));
+                }
+                true
+            }
+            DateRangeMsg::Start(msg) => {
+                self.preset = None;
+                self.start
+                    .update(msg, &mut orders.proxy(DateRangeMsg::Start))
+            }
+            DateRangeMsg::End(msg) => {
+                self.preset = None;
+                self.end.update(msg, &mut orders.proxy(DateRangeMsg::End))
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        self.preset = None;
+        self.start.reset();
+        self.end.reset();
+    }
+
+    fn value(&self) -> Self::Value {
+        let (start, end) = (self.start.value()?, self.end.value()?);
+        match start <= end {
+            true => Some((start, end)),
+            false => None,
+        }
+    }
+
+    fn set_value(&mut self, value: Self::Value) {
+        self.preset = None;
+        self.start.set_value(value.map(|x| x.0));
+        self.end.set_value(value.map(|x| x.1));
+    }
+
+    fn state(&self) -> Self::State {
+        (self.preset, self.start.state(), self.end.state())
+    }
+
+    fn set_state(&mut self, (preset, start, end): Self::State) {
+        self.preset = preset;
+        self.start.set_state(start);
+        self.end.set_state(end);
+    }
+
+    fn has_changed(&self) -> bool {
+        self.start.has_changed() || self.end.has_changed()
+    }
+
+    fn view(&self, disabled: bool) -> Node<Self::Msg> {
+        let error = self.error();
+
+        div![
+            C!["field"],
+            IF!(!self.label.is_empty() => label![C!["label"], &self.label]),
+            div![
+                C!["field", "is-grouped", "is-grouped-multiline", "mb-0"],
+                div![
+                    C!["control"],
+                    label![C!["label"], "Preset"],
+                    self.view_presets(disabled)
+                ],
+                div![
+                    C!["control"],
+                    self.start.view(disabled).map_msg(DateRangeMsg::Start)
+                ],
+                div![
+                    C!["control"],
+                    self.end.view(disabled).map_msg(DateRangeMsg::End)
+                ],
+            ],
+            p![C!["help", "is-danger"], error.unwrap_or_default()],
+        ]
+    }
+
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn display_value(&self) -> String {
+        text(self.start.display_value(), self.end.display_value())
+    }
+
+    fn display_initial(&self) -> String {
+        text(self.start.display_initial(), self.end.display_initial())
+    }
+
+    /// Both dates separated by a comma, e.g. `2024-01-01,2024-01-31`.
+    fn query(&self) -> Option<String> {
+        self.value()
+            .map(|(start, end)| format!("{},{}", start, end))
+    }
+
+    fn set_query(&mut self, query: &str) {
+        let mut dates = query.split(',').map(str::parse::<NaiveDate>);
+        if let (Some(Ok(start)), Some(Ok(end)), None) = (dates.next(), dates.next(), dates.next()) {
+            if start <= end {
+                self.set_value(Some((start, end)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2021, month, day).unwrap()
+    }
+
+    #[test]
+    fn last_days_include_today() {
+        let today = date(3, 10);
+        assert_eq!(Preset::LastDays(7).range(today), Some((date(3, 4), today)));
+        assert_eq!(Preset::LastDays(1).range(today), Some((today, today)));
+        assert_eq!(Preset::LastDays(0).range(today), Some((today, today)));
+    }
+
+    #[test]
+    fn calendar_presets() {
+        // 2021-03-10 is a wednesday
+        let today = date(3, 10);
+        assert_eq!(Preset::Today.range(today), Some((today, today)));
+        assert_eq!(Preset::ThisWeek.range(today), Some((date(3, 8), today)));
+        assert_eq!(Preset::ThisMonth.range(today), Some((date(3, 1), today)));
+        assert_eq!(
+            Preset::LastMonth.range(today),
+            Some((date(2, 1), date(2, 28)))
+        );
+        assert_eq!(Preset::ThisYear.range(today), Some((date(1, 1), today)));
+        assert_eq!(Preset::Custom.range(today), None);
+    }
+
+    #[test]
+    fn last_month_in_january() {
+        let today = date(1, 15);
+        let december = |day| NaiveDate::from_ymd_opt(2020, 12, day).unwrap();
+        assert_eq!(
+            Preset::LastMonth.range(today),
+            Some((december(1), december(31)))
+        );
+    }
+}
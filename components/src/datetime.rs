@@ -0,0 +1,338 @@
+use chrono::{Locale, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use seed::{prelude::*, *};
+use std::fmt::Debug;
+
+use crate::field::Field;
+use crate::input::error;
+
+/// A chrono type that can be edited through one of the native date/time inputs.
+pub trait Temporal: Copy + PartialOrd + Debug + 'static {
+    /// The `type` attribute of the native input.
+    const KIND: &'static str;
+    const INVALID: &'static str;
+
+    fn parse(str: &str) -> Option<Self>;
+
+    /// Formats the value the way the native input expects it.
+    fn html(&self) -> String;
+
+    fn localized(&self, locale: Locale) -> String;
+}
+
+impl Temporal for NaiveDate {
+    const KIND: &'static str = "date";
+    const INVALID: &'static str = "Must be a valid date.";
+
+    fn parse(str: &str) -> Option<Self> {
+        NaiveDate::parse_from_str(str, "%Y-%m-%d").ok()
+    }
+
+    fn html(&self) -> String {
+        self.format("%Y-%m-%d").to_string()
+    }
+
+    fn localized(&self, locale: Locale) -> String {
+        self.format_localized("%x", locale).to_string()
+    }
+}
+
+impl Temporal for NaiveTime {
+    const KIND: &'static str = "time";
+    const INVALID: &'static str = "Must be a valid time.";
+
+    fn parse(str: &str) -> Option<Self> {
+        NaiveTime::parse_from_str(str, "%H:%M:%S")
+            .or_else(|_| NaiveTime::parse_from_str(str, "%H:%M"))
+            .ok()
+    }
+
+    fn html(&self) -> String {
+        self.format("%H:%M:%S").to_string()
+    }
+
+    fn localized(&self, locale: Locale) -> String {
+        let datetime = NaiveDate::default().and_time(*self);
+        Utc.from_utc_datetime(&datetime)
+            .format_localized("%X", locale)
+            .to_string()
+    }
+}
+
+impl Temporal for NaiveDateTime {
+    const KIND: &'static str = "datetime-local";
+    const INVALID: &'static str = "Must be a valid date and time.";
+
+    fn parse(str: &str) -> Option<Self> {
+        NaiveDateTime::parse_from_str(str, "%Y-%m-%dT%H:%M:%S")
+            .or_else(|_| NaiveDateTime::parse_from_str(str, "%Y-%m-%dT%H:%M"))
+            .ok()
+    }
+
+    fn html(&self) -> String {
+        self.format("%Y-%m-%dT%H:%M:%S").to_string()
+    }
+
+    fn localized(&self, locale: Locale) -> String {
+        Utc.from_utc_datetime(self)
+            .format_localized("%c", locale)
+            .to_string()
+    }
+}
+
+pub enum PickerMsg {
+    Value(String),
+    Clear,
+    Blur,
+}
+
+pub type DateField = PickerField<NaiveDate>;
+pub type TimeField = PickerField<NaiveTime>;
+pub type DateTimeField = PickerField<NaiveDateTime>;
+
+pub struct PickerField<T: Temporal> {
+    label: String,
+
+    value: String,
+    initial: Option<T>,
+
+    min: Option<T>,
+    max: Option<T>,
+    locale: Option<Locale>,
+
+    untouched: bool,
+    optional: bool,
+}
+
+impl<T: Temporal> PickerField<T> {
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            value: String::new(),
+            initial: None,
+            min: None,
+            max: None,
+            locale: None,
+            untouched: true,
+            optional: false,
+        }
+    }
+
+    pub fn initial(mut self, initial: T) -> Self {
+        self.value = initial.html();
+        self.initial = Some(initial);
+        self
+    }
+
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+
+    pub fn min(mut self, min: T) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    pub fn max(mut self, max: T) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Shows the current value formatted for the given locale below the input.
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.locale = Some(locale);
+        self
+    }
+
+    fn display(&self, value: &T) -> String {
+        match self.locale {
+            Some(locale) => value.localized(locale),
+            None => value.html(),
+        }
+    }
+
+    fn validate(&self, str: &str) -> Result<T, String> {
+        let value = T::parse(str).ok_or_else(|| T::INVALID.to_owned())?;
+
+        match (self.min, self.max) {
+            (Some(min), _) if value < min => {
+                Err(format!("Must be at least {}.", self.display(&min)))
+            }
+            (_, Some(max)) if value > max => {
+                Err(format!("Must be at most {}.", self.display(&max)))
+            }
+            _ => Ok(value),
+        }
+    }
+}
+
+impl<T: Temporal> Field for PickerField<T> {
+    type Msg = PickerMsg;
+    type Value = Option<T>;
+    type State = String;
+
+    fn update(&mut self, msg: Self::Msg, _: &mut impl Orders<Self::Msg>) -> bool {
+        self.untouched = false;
+
+        self.value = match msg {
+            PickerMsg::Value(str) => str,
+            PickerMsg::Clear => String::new(),
+            PickerMsg::Blur => return false,
+        };
+        true
+    }
+
+    fn reset(&mut self) {
+        self.value = match &self.initial {
+            Some(x) => x.html(),
+            None => String::new(),
+        }
+    }
+
+    fn value(&self) -> Self::Value {
+        match self.value.is_empty() {
+            true => None,
+            false => self.validate(&self.value).ok(),
+        }
+    }
+
+    fn set_value(&mut self, value: Self::Value) {
+        self.value = value.map(|x| x.html()).unwrap_or_default();
+    }
+
+    fn state(&self) -> Self::State {
+        self.value.clone()
+    }
+
+    fn set_state(&mut self, state: Self::State) {
+        self.value = state;
+    }
+
+    fn has_changed(&self) -> bool {
+        self.initial != self.value()
+    }
+
+    fn view(&self, disabled: bool) -> Node<Self::Msg> {
+        let validator = |str: &str| self.validate(str);
+        let error = error(&validator, &self.value, self.untouched, self.optional);
+        let danger = error.is_some();
+
+        let formatted = match (self.locale, error.is_none()) {
+            (Some(_), true) => self.value().map(|x| self.display(&x)),
+            _ => None,
+        };
+
+        div![
+            C!["field"],
+            label![
+                C!["label"],
+                &self.label,
+                IF!(self.optional => i![C!["has-text-grey"], " - Optional"])
+            ],
+            div![
+                C!["field", "has-addons", "mb-0"],
+                div![
+                    C!["control", "is-expanded"],
+                    IF!(!danger => C!["mb-2"]),
+                    input![
+                        C!["input", IF!(danger => "is-danger")],
+                        input_ev(Ev::Input, PickerMsg::Value),
+                        ev(Ev::Blur, |_| PickerMsg::Blur),
+                        attrs! {At::Type => T::KIND, At::Value => &self.value},
+                        self.min.map(|x| attrs! {At::Min => x.html()}),
+                        self.max.map(|x| attrs! {At::Max => x.html()}),
+                        IF!(disabled => attrs! {At::Disabled => ""}),
+                    ],
+                ],
+                IF!(self.optional => div![
+                    C!["control"],
+                    button![
+                        C!["button"],
+                        attrs! {At::Disabled => (disabled || self.value.is_empty()).as_at_value()},
+                        ev(Ev::Click, |_| PickerMsg::Clear),
+                        span![C!["icon", "is-small"], i![C!["fas", "fa-times"]]]
+                    ]
+                ]),
+            ],
+            formatted.map(|x| p![C!["help"], x]),
+            p![C!["help", "is-danger"], error.unwrap_or_default()],
+        ]
+    }
+
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn display_value(&self) -> String {
+        self.value().map(|x| self.display(&x)).unwrap_or_default()
+    }
+
+    fn display_initial(&self) -> String {
+        self.initial.map(|x| self.display(&x)).unwrap_or_default()
+    }
+
+    fn query(&self) -> Option<String> {
+        self.value().map(|_| self.value.clone())
+    }
+
+    fn set_query(&mut self, query: &str) {
+        if self.validate(query).is_ok() {
+            self.value = query.to_owned();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn time(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn parses_html_values() {
+        let date = date(2024, 2, 29);
+        assert_eq!(NaiveDate::parse("2024-02-29"), Some(date));
+        assert_eq!(NaiveDate::parse("2023-02-29"), None);
+
+        let time = time(10, 0);
+        assert_eq!(NaiveTime::parse("10:00"), Some(time));
+        assert_eq!(NaiveTime::parse("10:00:00"), Some(time));
+        assert_eq!(NaiveTime::parse("25:00"), None);
+
+        let datetime = date.and_time(time);
+        assert_eq!(NaiveDateTime::parse("2024-02-29T10:00"), Some(datetime));
+        assert_eq!(NaiveDateTime::parse(&datetime.html()), Some(datetime));
+    }
+
+    #[test]
+    fn validates_min_and_max() {
+        let field = DateField::new("day")
+            .min(date(2024, 1, 1))
+            .max(date(2024, 12, 31));
+        assert!(field.validate("2024-06-01").is_ok());
+        assert_eq!(
+            field.validate("2023-12-31"),
+            Err("Must be at least 2024-01-01.".to_owned())
+        );
+        assert_eq!(
+            field.validate("2025-01-01"),
+            Err("Must be at most 2024-12-31.".to_owned())
+        );
+    }
+
+    #[test]
+    fn equal_times_are_unchanged() {
+        let mut field = TimeField::new("start").initial(time(10, 0));
+        field.set_query("10:00");
+        assert!(!field.has_changed());
+
+        field.set_query("10:30");
+        assert!(field.has_changed());
+    }
+}
@@ -0,0 +1,313 @@
+use seed::{prelude::*, *};
+use std::time::Duration;
+
+use crate::field::Field;
+use crate::input::{error, InputMsg};
+
+pub static INVALID_DURATION: &str = "Must be a duration like 1h 30m, 90m, 1.5h or 01:30:00.";
+
+fn unit(str: &str) -> Option<f64> {
+    match str {
+        "d" | "day" | "days" => Some(86400.),
+        "h" | "hr" | "hrs" | "hour" | "hours" => Some(3600.),
+        "m" | "min" | "mins" | "minute" | "minutes" => Some(60.),
+        "s" | "sec" | "secs" | "second" | "seconds" => Some(1.),
+        "ms" | "millis" | "milliseconds" => Some(0.001),
+        _ => None,
+    }
+}
+
+fn parse_clock(str: &str) -> Option<Duration> {
+    let parts: Option<Vec<u64>> = str.split(':').map(|x| x.trim().parse().ok()).collect();
+    let (h, m, s) = match parts?.as_slice() {
+        [h, m] if *m < 60 => (*h, *m, 0),
+        [h, m, s] if *m < 60 && *s < 60 => (*h, *m, *s),
+        _ => return None,
+    };
+    let seconds = h.checked_mul(3600)?.checked_add(m * 60 + s)?;
+    Some(Duration::from_secs(seconds))
+}
+
+fn parse_units(str: &str) -> Option<Duration> {
+    let mut seconds = 0.;
+    let mut rest = str.trim();
+
+    while !rest.is_empty() {
+        let number = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let amount: f64 = rest[..number].parse().ok()?;
+        rest = rest[number..].trim_start();
+
+        let name = rest
+            .find(|c: char| !c.is_alphabetic())
+            .unwrap_or(rest.len());
+        seconds += amount * unit(&rest[..name])?;
+        rest = rest[name..].trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+    }
+    // Casting would saturate, so larger totals are rejected instead
+    let millis = (seconds * 1000.).round();
+    match millis.is_finite() && millis < u64::MAX as f64 {
+        true => Some(Duration::from_millis(millis as u64)),
+        false => None,
+    }
+}
+
+/// Parses human durations such as `1h 30m`, `90m`, `1.5h` or `01:30:00`.
+pub fn parse_duration(str: &str) -> Result<Duration, String> {
+    let str = str.trim().to_lowercase();
+    let parsed = match str.contains(':') {
+        true => parse_clock(&str),
+        false => parse_units(&str),
+    };
+    parsed
+        .filter(|_| !str.is_empty())
+        .ok_or_else(|| INVALID_DURATION.to_owned())
+}
+
+/// Formats a duration in its canonical form, for example `1d 2h 30m 15s 500ms`.
+/// Precision below milliseconds is dropped, which `parse_duration` never produces.
+pub fn format_duration(duration: &Duration) -> String {
+    let total = duration.as_secs();
+    let parts = [
+        (total / 86400, "d"),
+        (total % 86400 / 3600, "h"),
+        (total % 3600 / 60, "m"),
+        (total % 60, "s"),
+        (u64::from(duration.subsec_millis()), "ms"),
+    ];
+
+    let formatted: Vec<_> = parts
+        .iter()
+        .filter(|(amount, _)| *amount > 0)
+        .map(|(amount, unit)| format!("{}{}", amount, unit))
+        .collect();
+
+    match formatted.is_empty() {
+        true => "0s".to_owned(),
+        false => formatted.join(" "),
+    }
+}
+
+pub struct DurationField {
+    label: String,
+    placeholder: String,
+
+    value: String,
+    initial: Option<Duration>,
+
+    min: Option<Duration>,
+    max: Option<Duration>,
+
+    untouched: bool,
+    optional: bool,
+}
+
+impl DurationField {
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            placeholder: "1h 30m".to_owned(),
+            value: String::new(),
+            initial: None,
+            min: None,
+            max: None,
+            untouched: true,
+            optional: false,
+        }
+    }
+
+    pub fn initial(mut self, initial: Duration) -> Self {
+        self.value = format_duration(&initial);
+        self.initial = Some(initial);
+        self
+    }
+
+    pub fn min(mut self, min: Duration) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    pub fn max(mut self, max: Duration) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    fn validate(&self, str: &str) -> Result<Duration, String> {
+        let value = parse_duration(str)?;
+
+        match (self.min, self.max) {
+            (Some(min), _) if value < min => {
+                Err(format!("Must be at least {}.", format_duration(&min)))
+            }
+            (_, Some(max)) if value > max => {
+                Err(format!("Must be at most {}.", format_duration(&max)))
+            }
+            _ => Ok(value),
+        }
+    }
+}
+
+impl Field for DurationField {
+    type Msg = InputMsg;
+    type Value = Option<Duration>;
+    type State = String;
+
+    fn update(&mut self, msg: Self::Msg, _: &mut impl Orders<Self::Msg>) -> bool {
+        self.untouched = false;
+
+        self.value = match msg {
+            InputMsg::Value(str) => str,
+            InputMsg::Submit => return false,
+            InputMsg::Blur => match self.validate(&self.value) {
+                Ok(duration) => format_duration(&duration),
+                Err(_) => return false,
+            },
+        };
+        true
+    }
+
+    fn reset(&mut self) {
+        self.value = match &self.initial {
+            Some(x) => format_duration(x),
+            None => String::new(),
+        }
+    }
+
+    fn value(&self) -> Self::Value {
+        match self.value.is_empty() {
+            true => None,
+            false => self.validate(&self.value).ok(),
+        }
+    }
+
+    fn set_value(&mut self, value: Self::Value) {
+        self.value = value.map(|x| format_duration(&x)).unwrap_or_default();
+    }
+
+    fn state(&self) -> Self::State {
+        self.value.clone()
+    }
+
+    fn set_state(&mut self, state: Self::State) {
+        self.value = state;
+    }
+
+    fn has_changed(&self) -> bool {
+        self.initial != self.value()
+    }
+
+    fn view(&self, disabled: bool) -> Node<Self::Msg> {
+        let validator = |str: &str| self.validate(str);
+        let error = error(&validator, &self.value, self.untouched, self.optional);
+        let danger = error.is_some();
+
+        div![
+            C!["field"],
+            label![
+                C!["label"],
+                &self.label,
+                IF!(self.optional => i![C!["has-text-grey"], " - Optional"])
+            ],
+            div![
+                C!["control", "has-icons-left"],
+                IF!(!danger => C!["mb-2"]),
+                input![
+                    C!["input", IF!(danger => "is-danger")],
+                    input_ev(Ev::Input, InputMsg::Value),
+                    ev(Ev::Blur, |_| InputMsg::Blur),
+                    attrs! {At::Value => &self.value, At::Placeholder => &self.placeholder},
+                    IF!(disabled => attrs! {At::Disabled => ""}),
+                ],
+                span![C!["icon", "is-small", "is-left"], i![C!["fas", "fa-clock"]]],
+            ],
+            p![C!["help", "is-danger"], error.unwrap_or_default()],
+        ]
+    }
+
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn display_value(&self) -> String {
+        self.value()
+            .map(|x| format_duration(&x))
+            .unwrap_or_default()
+    }
+
+    fn display_initial(&self) -> String {
+        self.initial
+            .map(|x| format_duration(&x))
+            .unwrap_or_default()
+    }
+
+    /// The canonical form, e.g. `1h 30m`.
+    fn query(&self) -> Option<String> {
+        self.value().map(|x| format_duration(&x))
+    }
+
+    fn set_query(&mut self, query: &str) {
+        if self.validate(query).is_ok() {
+            self.value = query.to_owned();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_units_and_clocks() {
+        assert_eq!(parse_duration("1h 30m"), Ok(Duration::from_secs(5400)));
+        assert_eq!(parse_duration("90 min"), Ok(Duration::from_secs(5400)));
+        assert_eq!(parse_duration("1.5h"), Ok(Duration::from_secs(5400)));
+        assert_eq!(parse_duration("01:30:00"), Ok(Duration::from_secs(5400)));
+        assert_eq!(parse_duration("1:30"), Ok(Duration::from_secs(5400)));
+        assert_eq!(parse_duration("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("250ms"), Ok(Duration::from_millis(250)));
+    }
+
+    #[test]
+    fn rejects_invalid_durations() {
+        for str in &[
+            "",
+            "1x",
+            "1:60",
+            "1:00:60",
+            "h",
+            "18446744073709551615:00",
+            "99999999999999999999d",
+        ] {
+            assert!(parse_duration(str).is_err(), "{}", str);
+        }
+    }
+
+    #[test]
+    fn formats_canonically() {
+        assert_eq!(format_duration(&Duration::from_secs(0)), "0s");
+        assert_eq!(
+            format_duration(&Duration::from_secs(95415)),
+            "1d 2h 30m 15s"
+        );
+        assert_eq!(format_duration(&Duration::from_millis(1500)), "1s 500ms");
+    }
+
+    #[test]
+    fn round_trips() {
+        for &millis in &[0, 1, 999, 1500, 60_000, 5_400_250, 90_061_001] {
+            let duration = Duration::from_millis(millis);
+            assert_eq!(parse_duration(&format_duration(&duration)), Ok(duration));
+        }
+    }
+}
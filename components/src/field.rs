@@ -1,16 +1,64 @@
-use seed::prelude::{Node, Orders};
+use seed::{prelude::*, *};
+
+use crate::form::FormViewOptions;
 
 pub static EMPTY: &str = "This field is required.";
 
 pub trait Field {
     type Msg: 'static;
     type Value;
+    /// The raw input, e.g. text which doesn't parse yet, restored exactly by `set_state`.
+    /// Passwords and files use `()`, which keeps them out of undo steps and drafts.
+    type State: Clone + PartialEq;
 
     fn update(&mut self, msg: Self::Msg, orders: &mut impl Orders<Self::Msg>) -> bool;
     fn reset(&mut self);
 
     fn value(&self) -> Self::Value;
+    /// Replaces the current value, e.g. when applying a value loaded from the backend.
+    fn set_value(&mut self, value: Self::Value);
+    fn state(&self) -> Self::State;
+    fn set_state(&mut self, state: Self::State);
     fn has_changed(&self) -> bool;
 
     fn view(&self, disabled: bool) -> Node<Self::Msg>;
+
+    fn label(&self) -> &str;
+
+    /// The current value formatted as text, for read-only views and summaries.
+    fn display_value(&self) -> String;
+    /// The initial value formatted like `display_value`.
+    fn display_initial(&self) -> String;
+
+    /// The current value as a url query parameter, `None` leaves the field out of the url.
+    /// Maps, lists and pairs keep this default as they have no single text form.
+    fn query(&self) -> Option<String> {
+        None
+    }
+    /// Restores the value from a query parameter written by `query`, ignoring invalid ones.
+    fn set_query(&mut self, _: &str) {}
+
+    /// Shows the label and the current value as plain text instead of an input.
+    fn view_readonly(&self) -> Node<Self::Msg> {
+        readonly(self.label(), self.display_value())
+    }
+
+    fn view_with(&self, options: FormViewOptions) -> Node<Self::Msg> {
+        match options.readonly {
+            true => self.view_readonly(),
+            false => self.view(options.disabled),
+        }
+    }
+}
+
+pub(crate) fn readonly<Msg>(label: &str, text: impl Into<String>) -> Node<Msg> {
+    let text = text.into();
+    div![
+        C!["field"],
+        IF!(!label.is_empty() => label![C!["label"], label]),
+        match text.is_empty() {
+            true => p![C!["mb-2", "has-text-grey"], "—"],
+            false => p![C!["mb-2"], style! {St::WhiteSpace => "pre-wrap"}, text],
+        }
+    ]
 }
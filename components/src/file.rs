@@ -0,0 +1,355 @@
+use seed::{prelude::*, *};
+use serde::{Deserialize, Serialize};
+use web_sys::{File, FileList, HtmlInputElement};
+
+use crate::field::{Field, EMPTY};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UploadedFile {
+    pub name: String,
+    pub mime: String,
+    pub bytes: Vec<u8>,
+}
+
+pub enum FileMsg {
+    Select(Vec<File>),
+    Loaded(usize, UploadedFile, Option<String>),
+    Failed(usize, String),
+    Remove(usize),
+    Drag(bool),
+}
+
+pub struct FileField {
+    label: String,
+
+    files: Vec<(UploadedFile, Option<String>)>,
+    errors: Vec<String>,
+    loading: usize,
+    // Bumped whenever the files are replaced, so reads still in flight are dropped
+    generation: usize,
+
+    accept: Vec<String>,
+    max_size: Option<u64>,
+    multiple: bool,
+
+    dragging: bool,
+    untouched: bool,
+    optional: bool,
+}
+
+fn to_vec(list: Option<FileList>) -> Vec<File> {
+    let list = match list {
+        Some(list) => list,
+        None => return Vec::new(),
+    };
+    (0..list.length()).filter_map(|i| list.get(i)).collect()
+}
+
+fn size(bytes: f64) -> String {
+    match bytes {
+        x if x >= 1e6 => format!("{:.1} MB", x / 1e6),
+        x if x >= 1e3 => format!("{:.1} kB", x / 1e3),
+        x => format!("{} B", x),
+    }
+}
+
+async fn read(file: File, generation: usize) -> FileMsg {
+    let name = file.name();
+    let buffer = match JsFuture::from(file.array_buffer()).await {
+        Ok(buffer) => buffer,
+        Err(_) => return FileMsg::Failed(generation, format!("Could not read {}.", name)),
+    };
+
+    let mime = file.type_();
+    let preview = match mime.starts_with("image/") {
+        true => web_sys::Url::create_object_url_with_blob(&file).ok(),
+        false => None,
+    };
+
+    let bytes = js_sys::Uint8Array::new(&buffer).to_vec();
+    FileMsg::Loaded(generation, UploadedFile { name, mime, bytes }, preview)
+}
+
+impl FileField {
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            files: Vec::new(),
+            errors: Vec::new(),
+            loading: 0,
+            generation: 0,
+            accept: Vec::new(),
+            max_size: None,
+            multiple: false,
+            dragging: false,
+            untouched: true,
+            optional: false,
+        }
+    }
+
+    /// Restricts the files to the given mime types (`image/*`, `text/csv`) or extensions (`.csv`).
+    pub fn accept(mut self, accept: &[&str]) -> Self {
+        self.accept = accept.iter().map(|x| x.to_lowercase()).collect();
+        self
+    }
+
+    /// The maximum size of a single file in bytes.
+    pub fn max_size(mut self, max_size: u64) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    pub fn multiple(mut self) -> Self {
+        self.multiple = true;
+        self
+    }
+
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+
+    fn validate(&self, file: &File) -> Result<(), String> {
+        let name = file.name().to_lowercase();
+        let mime = file.type_().to_lowercase();
+
+        let accepted = self.accept.is_empty()
+            || self.accept.iter().any(|x| match x.strip_suffix('*') {
+                Some(prefix) => mime.starts_with(prefix),
+                None if x.starts_with('.') => name.ends_with(x.as_str()),
+                None => &mime == x,
+            });
+        if !accepted {
+            return Err(format!("{} is not an accepted file type.", file.name()));
+        }
+
+        match self.max_size {
+            Some(max) if file.size() > max as f64 => Err(format!(
+                "{} is larger than {}.",
+                file.name(),
+                size(max as f64)
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Removes the files and drops the reads still in flight.
+    fn clear(&mut self) {
+        self.generation += 1;
+        self.loading = 0;
+        for (_, preview) in self.files.drain(..) {
+            if let Some(url) = preview {
+                let _ = web_sys::Url::revoke_object_url(&url);
+            }
+        }
+    }
+
+    fn view_file(
+        &self,
+        index: usize,
+        file: &UploadedFile,
+        preview: &Option<String>,
+        disabled: bool,
+    ) -> Node<FileMsg> {
+        div![
+            C!["media", "is-align-items-center"],
+            preview.as_ref().map(|url| {
+                figure![
+                    C!["media-left", "image", "is-48x48"],
+                    img![attrs! {At::Src => url, At::Alt => &file.name}]
+                ]
+            }),
+            div![
+                C!["media-content"],
+                p![&file.name],
+                p![C!["help"], size(file.bytes.len() as f64)]
+            ],
+            div![
+                C!["media-right"],
+                button![
+                    C!["delete"],
+                    attrs! {At::Type => "button", At::Disabled => disabled.as_at_value()},
+                    ev(Ev::Click, move |_| FileMsg::Remove(index))
+                ]
+            ]
+        ]
+    }
+}
+
+impl Field for FileField {
+    type Msg = FileMsg;
+    type Value = Vec<UploadedFile>;
+    type State = ();
+
+    fn update(&mut self, msg: Self::Msg, orders: &mut impl Orders<Self::Msg>) -> bool {
+        match msg {
+            FileMsg::Select(files) => {
+                self.untouched = false;
+                self.dragging = false;
+                self.errors.clear();
+
+                // A rejected file keeps the one selected before in single mode
+                let take = if self.multiple { files.len() } else { 1 };
+                for file in files.into_iter().take(take) {
+                    match self.validate(&file) {
+                        Ok(()) => {
+                            if !self.multiple {
+                                self.clear();
+                            }
+                            self.loading += 1;
+                            orders.perform_cmd(read(file, self.generation));
+                        }
+                        Err(err) => self.errors.push(err),
+                    }
+                }
+            }
+            FileMsg::Loaded(generation, file, preview) if generation == self.generation => {
+                self.loading -= 1;
+                if !self.multiple {
+                    self.clear();
+                }
+                self.files.push((file, preview));
+            }
+            FileMsg::Loaded(_, _, preview) => {
+                if let Some(url) = preview {
+                    let _ = web_sys::Url::revoke_object_url(&url);
+                }
+                return false;
+            }
+            FileMsg::Failed(generation, err) if generation == self.generation => {
+                self.loading -= 1;
+                self.errors.push(err);
+            }
+            FileMsg::Failed(..) => return false,
+            FileMsg::Remove(index) if index < self.files.len() => {
+                self.untouched = false;
+                if let (_, Some(url)) = self.files.remove(index) {
+                    let _ = web_sys::Url::revoke_object_url(&url);
+                }
+            }
+            FileMsg::Remove(_) => return false,
+            FileMsg::Drag(dragging) => {
+                self.dragging = dragging;
+                return false;
+            }
+        }
+        true
+    }
+
+    fn reset(&mut self) {
+        self.clear();
+        self.errors.clear();
+        self.untouched = true;
+    }
+
+    fn value(&self) -> Self::Value {
+        self.files.iter().map(|(file, _)| file.clone()).collect()
+    }
+
+    /// Replaces the files, previews of images are not recreated.
+    fn set_value(&mut self, value: Self::Value) {
+        self.clear();
+        self.files = value.into_iter().map(|file| (file, None)).collect();
+    }
+
+    fn state(&self) -> Self::State {}
+
+    fn set_state(&mut self, _: Self::State) {}
+
+    fn has_changed(&self) -> bool {
+        !self.files.is_empty()
+    }
+
+    fn view(&self, disabled: bool) -> Node<Self::Msg> {
+        let empty = !self.untouched && !self.optional && self.files.is_empty() && self.loading == 0;
+        let errors = self
+            .errors
+            .iter()
+            .map(String::as_str)
+            .chain(empty.then_some(EMPTY));
+
+        let text = match (self.loading, self.dragging) {
+            (0, false) => "Choose or drop a file…".to_owned(),
+            (0, true) => "Drop to upload".to_owned(),
+            (n, _) => format!("Reading {} file(s)…", n),
+        };
+
+        let drop = |event: web_sys::DragEvent| {
+            event.prevent_default();
+            let files = event.data_transfer().and_then(|x| x.files());
+            FileMsg::Select(to_vec(files))
+        };
+        let over = |event: web_sys::DragEvent| {
+            event.prevent_default();
+            FileMsg::Drag(true)
+        };
+
+        div![
+            C!["field"],
+            label![
+                C!["label"],
+                &self.label,
+                IF!(self.optional => i![C!["has-text-grey"], " - Optional"])
+            ],
+            div![
+                C![
+                    "file",
+                    "is-boxed",
+                    "is-fullwidth",
+                    IF!(self.dragging => "is-primary"),
+                    IF!(!self.errors.is_empty() || empty => "is-danger")
+                ],
+                IF!(!disabled => vec![
+                    drag_ev(Ev::DragOver, over),
+                    drag_ev(Ev::DragLeave, |_| FileMsg::Drag(false)),
+                    drag_ev(Ev::Drop, drop),
+                ]),
+                label![
+                    C!["file-label"],
+                    input![
+                        C!["file-input"],
+                        attrs! {At::Type => "file", At::Accept => self.accept.join(",")},
+                        IF!(self.multiple => attrs! {At::Multiple => ""}),
+                        IF!(disabled => attrs! {At::Disabled => ""}),
+                        ev(Ev::Change, |event| {
+                            let input = event.target()?.dyn_into::<HtmlInputElement>().ok()?;
+                            let files = to_vec(input.files());
+                            input.set_value("");
+                            Some(FileMsg::Select(files))
+                        }),
+                    ],
+                    span![
+                        C!["file-cta"],
+                        span![C!["file-icon"], i![C!["fas", "fa-upload"]]],
+                        span![C!["file-label"], text]
+                    ]
+                ]
+            ],
+            div![
+                C!["mt-2"],
+                self.files
+                    .iter()
+                    .enumerate()
+                    .map(|(index, (file, preview))| self.view_file(index, file, preview, disabled))
+            ],
+            errors.map(|err| p![C!["help", "is-danger"], err]),
+        ]
+    }
+
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn display_value(&self) -> String {
+        let names: Vec<_> = self
+            .files
+            .iter()
+            .map(|(file, _)| file.name.as_str())
+            .collect();
+        names.join("\n")
+    }
+
+    fn display_initial(&self) -> String {
+        String::new()
+    }
+}
@@ -1,14 +1,64 @@
-use seed::prelude::{Node, Orders};
+use seed::prelude::{Node, Orders, Url, UrlSearch};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FormViewOptions {
+    pub disabled: bool,
+    /// Renders values as plain text, e.g. for review and approval screens.
+    pub readonly: bool,
+}
 
 pub trait Form {
     type Msg: 'static;
     type Value;
+    /// The raw state of every field, including invalid input, used to restore the form later.
+    type Snapshot: Clone + PartialEq;
+    /// The values of the changed fields only, e.g. for PATCH requests.
+    type Patch;
 
     fn update(&mut self, msg: Self::Msg, orders: &mut impl Orders<Self::Msg>) -> bool;
     fn reset(&mut self);
 
     fn value(&self) -> Self::Value;
-    fn view(&self) -> Vec<Node<Self::Msg>>;
+
+    fn snapshot(&self) -> Self::Snapshot;
+    fn restore(&mut self, snapshot: Self::Snapshot);
+
+    fn changes(&self) -> Self::Patch;
+
+    fn view_with(&self, options: FormViewOptions) -> Vec<Node<Self::Msg>>;
+
+    fn view(&self) -> Vec<Node<Self::Msg>> {
+        self.view_with(FormViewOptions::default())
+    }
+
+    /// A table of every field and its value, highlighting the changed ones.
+    fn view_summary(&self) -> Node<Self::Msg>;
+
+    /// The name of each field, in the same order as the nodes returned by `view`.
+    fn names(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// The fields with a value as url query parameters, e.g. for filters which can be shared.
+    fn query(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+    /// Restores the fields which have a parameter in the query, the others keep their value.
+    fn restore_query(&mut self, _: &UrlSearch) {}
 
     fn has_changed(&self) -> bool;
 }
+
+/// Writes the form to the query of the current url, replacing the history entry so that
+/// editing a filter doesn't add a step to the back button for every key press.
+pub fn replace_query(form: &impl Form) {
+    let mut url = Url::current();
+    let search = url.search_mut();
+    for name in form.names() {
+        search.remove(name);
+    }
+    for (name, value) in form.query() {
+        search.insert(name, vec![value]);
+    }
+    url.go_and_replace();
+}
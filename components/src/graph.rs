@@ -4,26 +4,26 @@ pub struct Graph {
     id: String,
 }
 
-pub enum Msg {
+pub enum GraphMsg {
     Resize,
 }
 
 impl Graph {
-    pub fn new(id: impl Into<String>, orders: &mut impl Orders<Msg>) -> Self {
-        orders.stream(streams::window_event(Ev::Resize, |_| Msg::Resize));
+    pub fn new(id: impl Into<String>, orders: &mut impl Orders<GraphMsg>) -> Self {
+        orders.stream(streams::window_event(Ev::Resize, |_| GraphMsg::Resize));
 
         Self { id: id.into() }
     }
 
     pub fn redraw() {}
 
-    pub fn update(msg: Msg, _: &mut impl Orders<Msg>) {
+    pub fn update(msg: GraphMsg, _: &mut impl Orders<GraphMsg>) {
         match msg {
-            Msg::Resize => {}
+            GraphMsg::Resize => {}
         }
     }
 
-    pub fn view(&self) -> Node<Msg> {
+    pub fn view(&self) -> Node<GraphMsg> {
         canvas![attrs! {At::Id => &self.id}]
     }
 }
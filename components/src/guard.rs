@@ -0,0 +1,133 @@
+use seed::{prelude::*, *};
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use crate::button::Button;
+use crate::form::Form;
+
+pub enum GuardMsg {
+    Requested(Url),
+    KeepEditing,
+    Discard,
+}
+
+/// Asks for confirmation before leaving the page while a registered form has unsaved changes.
+///
+/// Links within the app are held back until the user chooses in a modal, closing or
+/// reloading the tab shows the confirmation dialog of the browser instead.
+/// The parent handles `GuardMsg::Discard` as well if its forms should be reset.
+pub struct NavigationGuard {
+    forms: BTreeMap<String, bool>,
+    pending: Option<Url>,
+
+    // Shared with the event handlers, which have to decide before the next update
+    changed: Rc<Cell<bool>>,
+
+    _unload: StreamHandle,
+    _urls: SubHandle,
+}
+
+impl NavigationGuard {
+    /// Create the guard in `init`, so it sees link clicks before the router of seed does.
+    pub fn new(orders: &mut impl Orders<GuardMsg>) -> Self {
+        let changed = Rc::new(Cell::new(false));
+
+        let unload = {
+            let changed = changed.clone();
+            move |event: web_sys::Event| {
+                if changed.get() {
+                    event.prevent_default();
+                    event
+                        .unchecked_into::<web_sys::BeforeUnloadEvent>()
+                        .set_return_value("");
+                }
+            }
+        };
+        let urls = {
+            let changed = changed.clone();
+            move |subs::UrlRequested(url, request)| {
+                if !changed.get() {
+                    return None;
+                }
+                request.handled_and_prevent_refresh();
+                Some(GuardMsg::Requested(url))
+            }
+        };
+
+        Self {
+            forms: BTreeMap::new(),
+            pending: None,
+            changed,
+            _unload: orders.stream_with_handle(streams::window_event(Ev::BeforeUnload, unload)),
+            _urls: orders.subscribe_with_handle(urls),
+        }
+    }
+
+    /// Registers the form under the given name, call this again after each update of the form.
+    pub fn watch(&mut self, name: impl Into<String>, form: &impl Form) {
+        self.forms.insert(name.into(), form.has_changed());
+        self.refresh();
+    }
+
+    pub fn forget(&mut self, name: &str) {
+        self.forms.remove(name);
+        self.refresh();
+    }
+
+    pub fn has_changed(&self) -> bool {
+        self.changed.get()
+    }
+
+    fn refresh(&mut self) {
+        self.changed
+            .set(self.forms.values().any(|&changed| changed));
+    }
+
+    pub fn update(&mut self, msg: GuardMsg, orders: &mut impl Orders<GuardMsg>) {
+        match msg {
+            GuardMsg::Requested(url) => self.pending = Some(url),
+            GuardMsg::KeepEditing => self.pending = None,
+            GuardMsg::Discard => {
+                self.forms.clear();
+                self.refresh();
+                if let Some(url) = self.pending.take() {
+                    orders.notify(subs::UrlRequested::new(url));
+                }
+            }
+        }
+    }
+
+    pub fn view(&self) -> Node<GuardMsg> {
+        div![
+            C!["modal", IF!(self.pending.is_some() => "is-active")],
+            div![
+                C!["modal-background"],
+                ev(Ev::Click, |_| GuardMsg::KeepEditing)
+            ],
+            div![
+                C!["modal-card"],
+                header![
+                    C!["modal-card-head"],
+                    p![C!["modal-card-title"], "Unsaved changes"]
+                ],
+                section![
+                    C!["modal-card-body"],
+                    "Your changes will be lost if you leave this page."
+                ],
+                footer![
+                    C!["modal-card-foot"],
+                    div![
+                        C!["buttons"],
+                        Button::custom("keep editing", "is-success", "fa-pen", || {
+                            GuardMsg::KeepEditing
+                        })
+                        .shown(),
+                        Button::custom("discard", "is-danger", "fa-trash", || GuardMsg::Discard)
+                            .shown(),
+                    ]
+                ]
+            ]
+        ]
+    }
+}
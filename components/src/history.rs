@@ -0,0 +1,199 @@
+use seed::{prelude::*, *};
+
+use crate::form::{Form, FormViewOptions};
+
+/// Changes closer together than this, in milliseconds, are undone as a single step.
+const COALESCE: f64 = 1000.;
+
+pub enum HistoryMsg<M> {
+    Form(M),
+    Undo,
+    Redo,
+}
+
+/// Adds undo and redo to a form, bound to Ctrl+Z and Ctrl+Shift+Z (or Ctrl+Y).
+///
+/// The shortcuts only apply while the focus is within one of the fields of the form,
+/// so other inputs on the page keep the undo of the browser.
+pub struct History<F: Form> {
+    form: F,
+
+    past: Vec<F::Snapshot>,
+    future: Vec<F::Snapshot>,
+    current: F::Snapshot,
+
+    last_change: f64,
+    limit: usize,
+}
+
+fn shortcut<M>(event: web_sys::KeyboardEvent) -> Option<HistoryMsg<M>> {
+    if !(event.ctrl_key() || event.meta_key()) {
+        return None;
+    }
+
+    let msg = match (event.key().to_lowercase().as_str(), event.shift_key()) {
+        ("z", false) => HistoryMsg::Undo,
+        ("z", true) | ("y", false) => HistoryMsg::Redo,
+        _ => return None,
+    };
+    // Forms nested in another `History` are undone by the innermost one only
+    event.prevent_default();
+    event.stop_propagation();
+    Some(msg)
+}
+
+impl<F: Form> History<F> {
+    pub fn new(form: F) -> Self {
+        Self {
+            current: form.snapshot(),
+            form,
+            past: Vec::new(),
+            future: Vec::new(),
+            last_change: 0.,
+            limit: 100,
+        }
+    }
+
+    /// The maximum number of steps that can be undone.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    pub fn form(&self) -> &F {
+        &self.form
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.past.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.future.is_empty()
+    }
+
+    pub fn undo(&mut self) -> bool {
+        let snapshot = match self.past.pop() {
+            Some(snapshot) => snapshot,
+            None => return false,
+        };
+        let current = std::mem::replace(&mut self.current, snapshot.clone());
+        self.future.push(current);
+        self.form.restore(snapshot);
+        self.last_change = 0.;
+        true
+    }
+
+    pub fn redo(&mut self) -> bool {
+        let snapshot = match self.future.pop() {
+            Some(snapshot) => snapshot,
+            None => return false,
+        };
+        let current = std::mem::replace(&mut self.current, snapshot.clone());
+        self.past.push(current);
+        self.form.restore(snapshot);
+        self.last_change = 0.;
+        true
+    }
+
+    /// Records the state of the form, merging it into the last step while typing.
+    fn record(&mut self) {
+        let snapshot = self.form.snapshot();
+        if snapshot == self.current {
+            return;
+        }
+
+        let now = js_sys::Date::now();
+        let coalesce = now - self.last_change < COALESCE;
+        self.last_change = now;
+
+        let previous = std::mem::replace(&mut self.current, snapshot);
+        if !coalesce {
+            self.past.push(previous);
+            if self.past.len() > self.limit {
+                self.past.remove(0);
+            }
+        }
+        self.future.clear();
+    }
+}
+
+impl<F: Form> Form for History<F> {
+    type Msg = HistoryMsg<F::Msg>;
+    type Value = F::Value;
+    type Snapshot = F::Snapshot;
+    type Patch = F::Patch;
+
+    fn update(&mut self, msg: Self::Msg, orders: &mut impl Orders<Self::Msg>) -> bool {
+        match msg {
+            HistoryMsg::Form(msg) => {
+                let changed = self.form.update(msg, &mut orders.proxy(HistoryMsg::Form));
+                if changed {
+                    self.record();
+                }
+                changed
+            }
+            HistoryMsg::Undo => self.undo(),
+            HistoryMsg::Redo => self.redo(),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.form.reset();
+        self.last_change = 0.;
+        self.record();
+    }
+
+    fn value(&self) -> Self::Value {
+        self.form.value()
+    }
+
+    fn snapshot(&self) -> Self::Snapshot {
+        self.form.snapshot()
+    }
+
+    fn restore(&mut self, snapshot: Self::Snapshot) {
+        self.form.restore(snapshot);
+        self.last_change = 0.;
+        self.record();
+    }
+
+    fn changes(&self) -> Self::Patch {
+        self.form.changes()
+    }
+
+    fn view_with(&self, options: FormViewOptions) -> Vec<Node<Self::Msg>> {
+        self.form
+            .view_with(options)
+            .into_iter()
+            .map(|node| {
+                div![
+                    node.map_msg(HistoryMsg::Form),
+                    keyboard_ev(Ev::KeyDown, shortcut::<F::Msg>),
+                ]
+            })
+            .collect()
+    }
+
+    fn view_summary(&self) -> Node<Self::Msg> {
+        self.form.view_summary().map_msg(HistoryMsg::Form)
+    }
+
+    fn names(&self) -> Vec<String> {
+        self.form.names()
+    }
+
+    fn query(&self) -> Vec<(String, String)> {
+        self.form.query()
+    }
+
+    fn restore_query(&mut self, search: &UrlSearch) {
+        self.form.restore_query(search);
+        self.last_change = 0.;
+        self.record();
+    }
+
+    fn has_changed(&self) -> bool {
+        self.form.has_changed()
+    }
+}
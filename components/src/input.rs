@@ -1,7 +1,9 @@
 use seed::{prelude::*, *};
 use std::fmt::Debug;
 
+use crate::button::Button;
 use crate::field::{Field, EMPTY};
+use crate::mask::Mask;
 
 #[derive(Debug, Clone)]
 pub enum State<T> {
@@ -12,9 +14,11 @@ pub enum State<T> {
 
 pub type Validator<T> = Box<dyn Fn(&str) -> Result<T, String>>;
 
-pub enum Msg {
+pub enum InputMsg {
     Blur,
     Value(String),
+    /// Sent by button addons and when pressing enter.
+    Submit,
 }
 
 pub struct InputField<T: ToString + Clone + PartialEq + Debug> {
@@ -25,11 +29,33 @@ pub struct InputField<T: ToString + Clone + PartialEq + Debug> {
     initial: Option<T>,
 
     validator: Validator<T>,
+    mask: Option<Mask>,
+
+    icon_left: Option<String>,
+    icon_right: Option<String>,
+    prefix: Option<String>,
+    suffix: Option<String>,
+    button: Option<Button<InputMsg>>,
+
+    input: ElRef<web_sys::HtmlInputElement>,
 
     untouched: bool,
     optional: bool,
 }
 
+pub(crate) fn error<T>(
+    validator: &impl Fn(&str) -> Result<T, String>,
+    value: &str,
+    untouched: bool,
+    optional: bool,
+) -> Option<String> {
+    match (untouched, optional, value.is_empty()) {
+        (true, _, _) | (_, true, true) => None,
+        (false, false, true) => Some(EMPTY.to_owned()),
+        (false, _, false) => validator(value).err(),
+    }
+}
+
 impl<T: ToString + Clone + PartialEq + Debug> InputField<T> {
     pub fn new(
         label: impl Into<String>,
@@ -41,6 +67,13 @@ impl<T: ToString + Clone + PartialEq + Debug> InputField<T> {
             value: String::new(),
             initial: None,
             validator: Box::new(validator),
+            mask: None,
+            icon_left: None,
+            icon_right: None,
+            prefix: None,
+            suffix: None,
+            button: None,
+            input: ElRef::new(),
             untouched: true,
             optional: false,
         }
@@ -61,6 +94,57 @@ impl<T: ToString + Clone + PartialEq + Debug> InputField<T> {
         self.placeholder = placeholder.into();
         self
     }
+
+    /// Formats the input while typing, the validator receives the text without separators.
+    pub fn mask(mut self, mask: impl Into<Mask>) -> Self {
+        let mask = mask.into();
+        self.value = mask.apply(&self.value).0;
+        self.mask = Some(mask);
+        self
+    }
+
+    pub fn icon_left(mut self, icon: impl Into<String>) -> Self {
+        self.icon_left = Some(icon.into());
+        self
+    }
+
+    /// Shows an icon on the right, which is replaced by the error icon while invalid.
+    pub fn icon_right(mut self, icon: impl Into<String>) -> Self {
+        self.icon_right = Some(icon.into());
+        self
+    }
+
+    /// Adds a static addon before the input, such as "https://" or "€".
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Adds a static addon after the input, such as "kg".
+    pub fn suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.suffix = Some(suffix.into());
+        self
+    }
+
+    /// Adds a button after the input, usually sending `InputMsg::Submit`.
+    pub fn button(mut self, button: Button<InputMsg>) -> Self {
+        self.button = Some(button);
+        self
+    }
+
+    fn raw(&self) -> String {
+        match &self.mask {
+            Some(mask) => mask.unmask(&self.value),
+            None => self.value.clone(),
+        }
+    }
+
+    fn masked(&self, str: &str) -> String {
+        match &self.mask {
+            Some(mask) => mask.apply(str).0,
+            None => str.to_owned(),
+        }
+    }
 }
 
 impl InputField<String> {
@@ -79,48 +163,104 @@ impl InputField<f64> {
 }
 
 impl<T: ToString + Clone + PartialEq + Debug> Field for InputField<T> {
-    type Msg = Msg;
+    type Msg = InputMsg;
     type Value = Option<T>;
+    type State = String;
 
-    fn update(&mut self, msg: Self::Msg, _: &mut impl Orders<Self::Msg>) -> bool {
+    fn update(&mut self, msg: Self::Msg, orders: &mut impl Orders<Self::Msg>) -> bool {
         self.untouched = false;
 
-        self.value = match msg {
-            Msg::Value(str) => str,
-            Msg::Blur => return false,
+        let str = match msg {
+            InputMsg::Value(str) => str,
+            InputMsg::Blur | InputMsg::Submit => return false,
+        };
+
+        self.value = match (&self.mask, self.input.get()) {
+            (Some(mask), Some(input)) => {
+                let caret = input.selection_start().ok().flatten().unwrap_or(0) as usize;
+                let caret = mask.caret(&str, caret) as u32;
+                orders.after_next_render(move |_| {
+                    let _ = input.set_selection_range(caret, caret);
+                });
+                mask.apply(&str).0
+            }
+            (Some(mask), None) => mask.apply(&str).0,
+            (None, _) => str,
         };
         true
     }
 
     fn reset(&mut self) {
-        self.value = match &self.initial {
-            Some(x) => x.to_string(),
-            None => String::new(),
+        self.value = match (&self.initial, &self.mask) {
+            (Some(x), Some(mask)) => mask.apply(&x.to_string()).0,
+            (Some(x), None) => x.to_string(),
+            (None, _) => String::new(),
         }
     }
 
     fn value(&self) -> Self::Value {
         match self.value.is_empty() {
             true => None,
-            false => (self.validator)(&self.value).ok(),
+            false => (self.validator)(&self.raw()).ok(),
         }
     }
 
+    fn set_value(&mut self, value: Self::Value) {
+        let value = value.map(|x| x.to_string()).unwrap_or_default();
+        self.value = self.masked(&value);
+    }
+
+    fn state(&self) -> Self::State {
+        self.value.clone()
+    }
+
+    fn set_state(&mut self, state: Self::State) {
+        self.value = state;
+    }
+
     fn has_changed(&self) -> bool {
-        match &self.initial {
-            Some(x) => x.to_string() != self.value,
-            None => !self.value.is_empty(),
-        }
+        let initial = match (&self.initial, &self.mask) {
+            (Some(x), Some(mask)) => mask.unmask(&x.to_string()),
+            (Some(x), None) => x.to_string(),
+            (None, _) => String::new(),
+        };
+        initial != self.raw()
     }
 
     fn view(&self, disabled: bool) -> Node<Self::Msg> {
-        let error = match (self.untouched, self.optional, self.value.is_empty()) {
-            (true, _, _) | (_, true, true) => None,
-            (false, false, true) => Some(EMPTY.to_owned()),
-            (false, _, false) => (self.validator)(&self.value).err(),
-        };
+        let error = error(&self.validator, &self.raw(), self.untouched, self.optional);
         let danger = error.is_some();
 
+        let icon_right = match danger {
+            true => Some("fa-exclamation-triangle"),
+            false => self.icon_right.as_deref(),
+        };
+        let addon = |text: &String| div![C!["control"], span![C!["button", "is-static"], text]];
+        let addons = self.prefix.is_some() || self.suffix.is_some() || self.button.is_some();
+
+        let control = div![
+            C!["control"],
+            IF!(addons => C!["is-expanded"]),
+            IF!(self.icon_left.is_some() => C!["has-icons-left"]),
+            IF!(icon_right.is_some() => C!["has-icons-right"]),
+            IF!(!danger && !addons => C!["mb-2"]), // use 5 for minimal error drift
+            input![
+                C!["input", IF!(danger => "is-danger")],
+                input_ev(Ev::Input, InputMsg::Value),
+                ev(Ev::Blur, |_| InputMsg::Blur),
+                keyboard_ev(Ev::KeyDown, |event| {
+                    IF!(event.key() == "Enter" => InputMsg::Submit)
+                }),
+                el_ref(&self.input),
+                attrs! {At::Placeholder => &self.placeholder, At::Value => &self.value},
+                IF!(disabled => attrs! {At::Disabled => ""}),
+            ],
+            self.icon_left
+                .as_ref()
+                .map(|icon| span![C!["icon is-small is-left"], i![C!["fas", icon]]]),
+            icon_right.map(|icon| span![C!["icon is-small is-right"], i![C!["fas", icon]]]),
+        ];
+
         div![
             C!["field"],
             label![
@@ -128,20 +268,79 @@ impl<T: ToString + Clone + PartialEq + Debug> Field for InputField<T> {
                 &self.label,
                 IF!(self.optional => i![C!["has-text-grey"], " - Optional"])
             ],
-            div![
-                C!["control"],
-                IF!(danger => C!["has-icons-right"]),
-                IF!(!danger => C!["mb-2"]), // use 5 for minimal error drift
-                input![
-                    C!["input", IF!(danger => "is-danger")],
-                    input_ev(Ev::Input, |str| Msg::Value(str)),
-                    ev(Ev::Blur, |_| Msg::Blur),
-                    attrs! {At::Placeholder => &self.placeholder},
-                    IF!(disabled => attrs! {At::Disabled => ""}),
+            match addons {
+                true => div![
+                    C!["field", "has-addons", IF!(!danger => "mb-2")],
+                    self.prefix.as_ref().map(addon),
+                    control,
+                    self.suffix.as_ref().map(addon),
+                    self.button
+                        .as_ref()
+                        .map(|button| div![C!["control"], button.disabled(disabled)]),
                 ],
-                IF![danger => span![C!["icon is-small is-right"], i![C!["fas", "fa-exclamation-triangle"]]]],
-            ],
+                false => control,
+            },
             p![C!["help", "is-danger"], error.unwrap_or_default()],
         ]
     }
+
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn display_value(&self) -> String {
+        self.value.clone()
+    }
+
+    fn display_initial(&self) -> String {
+        let initial = self.initial.as_ref().map(T::to_string).unwrap_or_default();
+        match &self.mask {
+            Some(mask) => mask.apply(&initial).0,
+            None => initial,
+        }
+    }
+
+    /// The unmasked text, only while it is valid.
+    fn query(&self) -> Option<String> {
+        self.value().map(|_| self.raw())
+    }
+
+    fn set_query(&mut self, query: &str) {
+        let value = self.masked(query);
+        let raw = match &self.mask {
+            Some(mask) => mask.unmask(&value),
+            None => value.clone(),
+        };
+        if (self.validator)(&raw).is_ok() {
+            self.value = value;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mask::MaskPreset;
+
+    #[test]
+    fn invalid_input_is_left_out_of_queries() {
+        let mut field = InputField::<i64>::new("count", |x| {
+            x.parse().map_err(|_| "Must be a number.".to_owned())
+        });
+        field.set_state("12a".to_owned());
+        assert_eq!(field.query(), None);
+
+        field.set_query("x");
+        assert_eq!(field.state(), "12a");
+        field.set_query("12");
+        assert_eq!(field.query().as_deref(), Some("12"));
+    }
+
+    #[test]
+    fn masked_queries_are_unmasked() {
+        let mut field = InputField::string("card").mask(MaskPreset::CardExpiry);
+        field.set_query("1225");
+        assert_eq!(field.state(), "12/25");
+        assert_eq!(field.query().as_deref(), Some("1225"));
+    }
 }
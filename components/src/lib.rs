@@ -1,17 +1,59 @@
+mod builder;
 mod button;
+mod color;
+mod combinators;
+mod controller;
+mod daterange;
+mod datetime;
+mod duration;
 mod field;
+mod file;
 mod form;
 mod graph;
+mod guard;
+mod history;
 mod input;
+mod map;
+mod mask;
+mod money;
+mod password;
+mod persisted;
+mod range;
 mod select;
 mod slider;
+mod stylesheet;
+mod summary;
+mod textarea;
 mod toggle;
+mod tuple;
 
+pub use builder::*;
 pub use button::*;
+pub use color::*;
+pub use combinators::*;
+pub use controller::*;
+pub use daterange::*;
+pub use datetime::*;
+pub use duration::*;
 pub use field::*;
+pub use file::*;
 pub use form::*;
 pub use graph::*;
+pub use guard::*;
+pub use history::*;
 pub use input::*;
+pub use map::*;
+pub use mask::*;
+pub use money::*;
+pub use password::*;
+pub use persisted::*;
+pub use range::*;
 pub use select::*;
 pub use slider::*;
+pub use summary::*;
+pub use textarea::*;
 pub use toggle::*;
+pub use tuple::*;
+
+// Derived forms with `#[serialize_patch]` serialize their patch through this re-export
+pub use serde;
@@ -0,0 +1,307 @@
+use seed::{prelude::*, *};
+use std::collections::BTreeMap;
+
+use crate::button::Button;
+use crate::field::{Field, EMPTY};
+use crate::input::{error, Validator};
+
+pub static DUPLICATE_KEY: &str = "Keys must be unique.";
+
+pub enum MapMsg {
+    Key(usize, String),
+    Value(usize, String),
+    Add,
+    Remove(usize),
+}
+
+struct Row {
+    key: String,
+    value: String,
+    untouched: bool,
+}
+
+impl Row {
+    /// Rows which were added but never filled in, these are left out of the value.
+    fn is_blank(&self) -> bool {
+        self.key.is_empty() && self.value.is_empty()
+    }
+
+    fn new(key: String, value: String) -> Self {
+        Self {
+            key,
+            value,
+            untouched: true,
+        }
+    }
+}
+
+fn lines<K: ToString, V: ToString>(map: &BTreeMap<K, V>) -> String {
+    let lines: Vec<_> = map
+        .iter()
+        .map(|(key, value)| format!("{}: {}", key.to_string(), value.to_string()))
+        .collect();
+    lines.join("\n")
+}
+
+/// Edits a map as rows of key and value inputs, e.g. for configuration or http headers.
+pub struct MapField<K: Ord + Clone + ToString, V: Clone + PartialEq + ToString> {
+    label: String,
+
+    rows: Vec<Row>,
+    initial: BTreeMap<K, V>,
+
+    key_validator: Validator<K>,
+    value_validator: Validator<V>,
+
+    untouched: bool,
+    optional: bool,
+}
+
+impl<K: Ord + Clone + ToString, V: Clone + PartialEq + ToString> MapField<K, V> {
+    pub fn new(
+        label: impl Into<String>,
+        key_validator: impl Fn(&str) -> Result<K, String> + 'static,
+        value_validator: impl Fn(&str) -> Result<V, String> + 'static,
+    ) -> Self {
+        Self {
+            label: label.into(),
+            rows: Vec::new(),
+            initial: BTreeMap::new(),
+            key_validator: Box::new(key_validator),
+            value_validator: Box::new(value_validator),
+            untouched: true,
+            optional: false,
+        }
+    }
+
+    pub fn initial(mut self, initial: BTreeMap<K, V>) -> Self {
+        self.initial = initial;
+        self.reset();
+        self
+    }
+
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+
+    fn is_duplicate(&self, index: usize) -> bool {
+        let key = match (self.key_validator)(&self.rows[index].key) {
+            Ok(key) => key,
+            Err(_) => return false,
+        };
+        self.rows[..index]
+            .iter()
+            .any(|row| (self.key_validator)(&row.key).ok().as_ref() == Some(&key))
+    }
+
+    fn row_error(&self, index: usize) -> Option<String> {
+        let row = &self.rows[index];
+        if row.is_blank() {
+            return None;
+        }
+        error(&self.key_validator, &row.key, row.untouched, false)
+            .or_else(|| error(&self.value_validator, &row.value, row.untouched, false))
+            .or_else(|| self.is_duplicate(index).then(|| DUPLICATE_KEY.to_owned()))
+    }
+
+    fn view_row(&self, index: usize, row: &Row, disabled: bool) -> Node<MapMsg> {
+        let error = self.row_error(index);
+        let danger = error.is_some();
+
+        let input = |value: &String, placeholder: &str, msg: fn(usize, String) -> MapMsg| {
+            div![
+                C!["control", "is-expanded"],
+                input![
+                    C!["input", IF!(danger => "is-danger")],
+                    input_ev(Ev::Input, move |str| msg(index, str)),
+                    attrs! {At::Value => value, At::Placeholder => placeholder},
+                    IF!(disabled => attrs! {At::Disabled => ""}),
+                ]
+            ]
+        };
+
+        div![
+            C!["mb-2"],
+            div![
+                C!["field", "is-grouped", "mb-0"],
+                input(&row.key, "Key", MapMsg::Key),
+                input(&row.value, "Value", MapMsg::Value),
+                div![
+                    C!["control"],
+                    Button::trash(move || MapMsg::Remove(index)).disabled(disabled)
+                ]
+            ],
+            error.map(|err| p![C!["help", "is-danger"], err]),
+        ]
+    }
+}
+
+impl MapField<String, String> {
+    pub fn strings(label: impl Into<String>) -> Self {
+        Self::new(label, |x| Ok(x.to_owned()), |x| Ok(x.to_owned()))
+    }
+}
+
+impl<K: Ord + Clone + ToString, V: Clone + PartialEq + ToString> Field for MapField<K, V> {
+    type Msg = MapMsg;
+    type Value = Option<BTreeMap<K, V>>;
+    type State = Vec<(String, String)>;
+
+    fn update(&mut self, msg: Self::Msg, _: &mut impl Orders<Self::Msg>) -> bool {
+        self.untouched = false;
+
+        match msg {
+            MapMsg::Key(index, key) if index < self.rows.len() => {
+                self.rows[index].key = key;
+                self.rows[index].untouched = false;
+            }
+            MapMsg::Value(index, value) if index < self.rows.len() => {
+                self.rows[index].value = value;
+                self.rows[index].untouched = false;
+            }
+            MapMsg::Add => self.rows.push(Row::new(String::new(), String::new())),
+            MapMsg::Remove(index) if index < self.rows.len() => {
+                self.rows.remove(index);
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn reset(&mut self) {
+        self.rows = self
+            .initial
+            .iter()
+            .map(|(key, value)| Row::new(key.to_string(), value.to_string()))
+            .collect();
+        self.untouched = true;
+    }
+
+    /// `None` as long as any row is incomplete, invalid or repeats the key of another row.
+    fn value(&self) -> Self::Value {
+        let mut map = BTreeMap::new();
+        for row in self.rows.iter().filter(|row| !row.is_blank()) {
+            if row.key.is_empty() || row.value.is_empty() {
+                return None;
+            }
+            let key = (self.key_validator)(&row.key).ok()?;
+            let value = (self.value_validator)(&row.value).ok()?;
+            if map.insert(key, value).is_some() {
+                return None;
+            }
+        }
+        Some(map)
+    }
+
+    fn set_value(&mut self, value: Self::Value) {
+        self.rows = value
+            .unwrap_or_default()
+            .iter()
+            .map(|(key, value)| Row::new(key.to_string(), value.to_string()))
+            .collect();
+    }
+
+    fn state(&self) -> Self::State {
+        self.rows
+            .iter()
+            .map(|row| (row.key.clone(), row.value.clone()))
+            .collect()
+    }
+
+    fn set_state(&mut self, state: Self::State) {
+        self.rows = state
+            .into_iter()
+            .map(|(key, value)| Row::new(key, value))
+            .collect();
+    }
+
+    fn has_changed(&self) -> bool {
+        self.value().as_ref() != Some(&self.initial)
+    }
+
+    fn view(&self, disabled: bool) -> Node<Self::Msg> {
+        let empty = !self.untouched && !self.optional && self.rows.is_empty();
+
+        div![
+            C!["field"],
+            label![
+                C!["label"],
+                &self.label,
+                IF!(self.optional => i![C!["has-text-grey"], " - Optional"])
+            ],
+            self.rows
+                .iter()
+                .enumerate()
+                .map(|(index, row)| self.view_row(index, row, disabled)),
+            Button::create(|| MapMsg::Add).disabled(disabled),
+            IF!(empty => p![C!["help", "is-danger"], EMPTY]),
+        ]
+    }
+
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn display_value(&self) -> String {
+        self.value().as_ref().map(lines).unwrap_or_default()
+    }
+
+    fn display_initial(&self) -> String {
+        lines(&self.initial)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(rows: &[(&str, &str)]) -> MapField<String, i64> {
+        let mut field = MapField::new(
+            "limits",
+            |x| Ok(x.to_owned()),
+            |x| x.parse().map_err(|_| "Must be a number.".to_owned()),
+        );
+        field.rows = rows
+            .iter()
+            .map(|(key, value)| Row::new(key.to_string(), value.to_string()))
+            .collect();
+        field
+    }
+
+    #[test]
+    fn collects_valid_rows() {
+        let value = field(&[("b", "2"), ("a", "1")]).value().unwrap();
+        assert_eq!(
+            value.into_iter().collect::<Vec<_>>(),
+            [("a".to_owned(), 1), ("b".to_owned(), 2)]
+        );
+        assert_eq!(field(&[]).value(), Some(BTreeMap::new()));
+    }
+
+    #[test]
+    fn skips_blank_rows() {
+        let mut field = MapField::strings("headers");
+        field.rows.push(Row::new(String::new(), String::new()));
+        assert_eq!(field.value(), Some(BTreeMap::new()));
+        assert!(!field.has_changed());
+        assert_eq!(field.row_error(0), None);
+    }
+
+    #[test]
+    fn rejects_empty_keys_and_values() {
+        assert_eq!(field(&[("", "1")]).value(), None);
+
+        let mut strings = MapField::strings("headers");
+        strings
+            .rows
+            .push(Row::new("accept".to_owned(), String::new()));
+        assert_eq!(strings.value(), None);
+    }
+
+    #[test]
+    fn rejects_invalid_and_duplicate_rows() {
+        assert_eq!(field(&[("a", "1"), ("b", "x")]).value(), None);
+        assert_eq!(field(&[("a", "1"), ("a", "2")]).value(), None);
+    }
+}
@@ -0,0 +1,168 @@
+/// A formatting pattern for text inputs, `#` takes a digit, `A` a letter and `*` either.
+/// Any other character is a separator which is inserted automatically.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mask {
+    pattern: Vec<char>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MaskPreset {
+    Iban,
+    CardNumber,
+    CardExpiry,
+}
+
+impl From<&str> for Mask {
+    fn from(pattern: &str) -> Self {
+        Self {
+            pattern: pattern.chars().collect(),
+        }
+    }
+}
+
+impl From<MaskPreset> for Mask {
+    fn from(preset: MaskPreset) -> Self {
+        match preset {
+            MaskPreset::Iban => "AA## **** **** **** **** **** **** **".into(),
+            MaskPreset::CardNumber => "#### #### #### ####".into(),
+            MaskPreset::CardExpiry => "##/##".into(),
+        }
+    }
+}
+
+fn accepts(slot: char, c: char) -> bool {
+    match slot {
+        '#' => c.is_ascii_digit(),
+        'A' => c.is_alphabetic(),
+        _ => c.is_alphanumeric(),
+    }
+}
+
+fn is_slot(c: char) -> bool {
+    matches!(c, '#' | 'A' | '*')
+}
+
+impl Mask {
+    /// Formats the input, returning the masked text and the characters that filled the slots.
+    pub fn apply(&self, input: &str) -> (String, String) {
+        let input: Vec<char> = input.chars().collect();
+        let (mut masked, mut raw, mut pending) = (String::new(), String::new(), String::new());
+        let (mut next, mut index) = (0, 0);
+
+        while let Some(&slot) = self.pattern.get(index) {
+            if !is_slot(slot) {
+                // Separators are only shown once the slot after them is filled
+                let run: Vec<char> = self.pattern[index..]
+                    .iter()
+                    .copied()
+                    .take_while(|&c| !is_slot(c))
+                    .collect();
+                index += run.len();
+                pending.extend(&run);
+
+                // A typed run is only skipped as a whole, so the digits of a prefix like
+                // `+32 ` still fill the slots when the user types them without it
+                let rest = &input[next..];
+                if rest.starts_with(&run) || run.starts_with(rest) {
+                    next += run.len().min(rest.len());
+                }
+                continue;
+            }
+            index += 1;
+
+            let c = loop {
+                match input.get(next) {
+                    Some(&c) => {
+                        next += 1;
+                        if accepts(slot, c) {
+                            break Some(c.to_ascii_uppercase());
+                        }
+                    }
+                    None => break None,
+                }
+            };
+            match c {
+                Some(c) => {
+                    masked.push_str(&pending);
+                    masked.push(c);
+                    raw.push(c);
+                    pending.clear();
+                }
+                None => break,
+            }
+        }
+        (masked, raw)
+    }
+
+    pub fn unmask(&self, input: &str) -> String {
+        self.apply(input).1
+    }
+
+    /// Where the caret should go in the masked text, given its position in the typed text.
+    pub fn caret(&self, input: &str, caret: usize) -> usize {
+        let prefix: String = input.chars().take(caret).collect();
+        self.apply(&prefix).0.chars().count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inserts_separators() {
+        let expiry = Mask::from(MaskPreset::CardExpiry);
+        assert_eq!(
+            expiry.apply("1225"),
+            ("12/25".to_owned(), "1225".to_owned())
+        );
+        assert_eq!(
+            expiry.apply("12/25"),
+            ("12/25".to_owned(), "1225".to_owned())
+        );
+        assert_eq!(expiry.apply("12"), ("12".to_owned(), "12".to_owned()));
+        assert_eq!(
+            expiry.apply("122599"),
+            ("12/25".to_owned(), "1225".to_owned())
+        );
+    }
+
+    #[test]
+    fn skips_rejected_characters() {
+        let card = Mask::from(MaskPreset::CardNumber);
+        assert_eq!(card.unmask("4111-1111 x1111 1111"), "4111111111111111");
+        assert_eq!(card.apply("4111a1").0, "4111 1");
+    }
+
+    #[test]
+    fn uppercases_letters() {
+        let iban = Mask::from(MaskPreset::Iban);
+        assert_eq!(
+            iban.apply("de89370400440532013000").0,
+            "DE89 3704 0044 0532 0130 00"
+        );
+        assert_eq!(iban.apply("89de").0, "DE");
+    }
+
+    #[test]
+    fn literal_prefixes_do_not_eat_digits() {
+        let phone = Mask::from("+32 ### ## ## ##");
+        assert_eq!(phone.apply("3").0, "+32 3");
+        assert_eq!(phone.apply("2").0, "+32 2");
+        assert_eq!(phone.apply("345").0, "+32 345");
+        assert_eq!(
+            phone.apply("+32 345"),
+            ("+32 345".to_owned(), "345".to_owned())
+        );
+        assert_eq!(phone.apply("+32 4701234").0, "+32 470 12 34");
+        assert_eq!(phone.apply("+32").0, "");
+        assert_eq!(phone.caret("+32 345", 5), 5);
+    }
+
+    #[test]
+    fn moves_the_caret_past_separators() {
+        let expiry = Mask::from(MaskPreset::CardExpiry);
+        assert_eq!(expiry.caret("1225", 2), 2);
+        assert_eq!(expiry.caret("1225", 3), 4);
+    }
+}
@@ -0,0 +1,377 @@
+use seed::{prelude::*, *};
+
+use crate::field::Field;
+use crate::input::{error, InputMsg};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Currency {
+    pub code: &'static str,
+    pub symbol: &'static str,
+    pub decimals: u32,
+}
+
+impl Currency {
+    pub const EUR: Currency = Currency {
+        code: "EUR",
+        symbol: "€",
+        decimals: 2,
+    };
+    pub const USD: Currency = Currency {
+        code: "USD",
+        symbol: "$",
+        decimals: 2,
+    };
+    pub const GBP: Currency = Currency {
+        code: "GBP",
+        symbol: "£",
+        decimals: 2,
+    };
+    pub const CHF: Currency = Currency {
+        code: "CHF",
+        symbol: "CHF",
+        decimals: 2,
+    };
+    pub const JPY: Currency = Currency {
+        code: "JPY",
+        symbol: "¥",
+        decimals: 0,
+    };
+}
+
+/// The separators used to write amounts, `1,234.56` versus `1.234,56`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberFormat {
+    pub decimal: char,
+    pub group: char,
+}
+
+impl NumberFormat {
+    pub const DOT: NumberFormat = NumberFormat {
+        decimal: '.',
+        group: ',',
+    };
+    pub const COMMA: NumberFormat = NumberFormat {
+        decimal: ',',
+        group: '.',
+    };
+}
+
+/// An amount of money in minor units (cents), avoiding the rounding of floats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Money {
+    pub minor: i64,
+    pub currency: Currency,
+}
+
+impl Money {
+    pub fn new(minor: i64, currency: Currency) -> Self {
+        Self { minor, currency }
+    }
+
+    pub fn parse(str: &str, currency: Currency, format: NumberFormat) -> Result<Self, String> {
+        let invalid = || "Must be a valid amount.".to_owned();
+
+        let str: String = str.chars().filter(|c| !c.is_whitespace()).collect();
+        let (negative, str) = match str.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, str.as_str()),
+        };
+
+        let (whole, fraction) = match str.split_once(format.decimal) {
+            Some((whole, fraction)) => (whole, fraction),
+            None => (str, ""),
+        };
+
+        // Group separators are only accepted between thousands, so `12,50` is not `1250`
+        let mut groups = whole.split(format.group);
+        let first = groups.next().unwrap_or_default();
+        let grouped = whole.contains(format.group);
+        if grouped && (first.is_empty() || first.len() > 3 || groups.any(|x| x.len() != 3)) {
+            return Err(invalid());
+        }
+        let whole = &whole.replace(format.group, "");
+
+        let digits = |x: &str| x.chars().all(|c| c.is_ascii_digit());
+        if (whole.is_empty() && fraction.is_empty()) || !digits(whole) || !digits(fraction) {
+            return Err(invalid());
+        }
+        if fraction.len() > currency.decimals as usize {
+            return Err(match currency.decimals {
+                0 => format!("{} has no decimals.", currency.code),
+                n => format!("At most {} decimals are allowed.", n),
+            });
+        }
+
+        let scale = 10i64.pow(currency.decimals);
+        let whole: i64 = match whole.is_empty() {
+            true => 0,
+            false => whole.parse().map_err(|_| invalid())?,
+        };
+        let fraction: i64 = format!("{:0<width$}", fraction, width = currency.decimals as usize)
+            .parse()
+            .unwrap_or(0);
+
+        let minor = whole
+            .checked_mul(scale)
+            .and_then(|x| x.checked_add(fraction))
+            .ok_or_else(invalid)?;
+
+        Ok(Money::new(if negative { -minor } else { minor }, currency))
+    }
+
+    /// Writes the amount with grouped thousands and all decimals of the currency.
+    pub fn format(&self, format: NumberFormat) -> String {
+        let scale = 10u64.pow(self.currency.decimals);
+        let absolute = self.minor.unsigned_abs();
+
+        let whole = (absolute / scale).to_string();
+        let groups: Vec<_> = whole
+            .as_bytes()
+            .rchunks(3)
+            .rev()
+            .map(|x| String::from_utf8_lossy(x))
+            .collect();
+        let grouped = groups.join(&format.group.to_string());
+
+        let sign = if self.minor < 0 { "-" } else { "" };
+        match self.currency.decimals {
+            0 => format!("{}{}", sign, grouped),
+            n => format!(
+                "{}{}{}{:0width$}",
+                sign,
+                grouped,
+                format.decimal,
+                absolute % scale,
+                width = n as usize
+            ),
+        }
+    }
+}
+
+pub struct MoneyField {
+    label: String,
+    placeholder: String,
+
+    value: String,
+    initial: Option<Money>,
+
+    currency: Currency,
+    format: NumberFormat,
+
+    untouched: bool,
+    optional: bool,
+}
+
+impl MoneyField {
+    pub fn new(label: impl Into<String>, currency: Currency) -> Self {
+        Self {
+            label: label.into(),
+            placeholder: String::new(),
+            value: String::new(),
+            initial: None,
+            currency,
+            format: NumberFormat::DOT,
+            untouched: true,
+            optional: false,
+        }
+    }
+
+    pub fn initial(mut self, minor: i64) -> Self {
+        let initial = Money::new(minor, self.currency);
+        self.value = initial.format(self.format);
+        self.initial = Some(initial);
+        self
+    }
+
+    /// Sets the separators used for parsing and displaying the amount.
+    pub fn format(mut self, format: NumberFormat) -> Self {
+        self.format = format;
+        self.value = match self.initial {
+            Some(initial) => initial.format(format),
+            None => self.value,
+        };
+        self
+    }
+
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    fn text(&self, money: Option<Money>) -> String {
+        money
+            .map(|x| format!("{} {}", self.currency.symbol, x.format(self.format)))
+            .unwrap_or_default()
+    }
+
+    fn validate(&self, str: &str) -> Result<Money, String> {
+        Money::parse(str, self.currency, self.format)
+    }
+}
+
+impl Field for MoneyField {
+    type Msg = InputMsg;
+    type Value = Option<Money>;
+    type State = String;
+
+    fn update(&mut self, msg: Self::Msg, _: &mut impl Orders<Self::Msg>) -> bool {
+        self.untouched = false;
+
+        self.value = match msg {
+            InputMsg::Value(str) => str,
+            InputMsg::Submit => return false,
+            InputMsg::Blur => match self.validate(&self.value) {
+                Ok(money) => money.format(self.format),
+                Err(_) => return false,
+            },
+        };
+        true
+    }
+
+    fn reset(&mut self) {
+        self.value = match &self.initial {
+            Some(x) => x.format(self.format),
+            None => String::new(),
+        }
+    }
+
+    fn value(&self) -> Self::Value {
+        match self.value.is_empty() {
+            true => None,
+            false => self.validate(&self.value).ok(),
+        }
+    }
+
+    fn set_value(&mut self, value: Self::Value) {
+        self.value = value.map(|x| x.format(self.format)).unwrap_or_default();
+    }
+
+    fn state(&self) -> Self::State {
+        self.value.clone()
+    }
+
+    fn set_state(&mut self, state: Self::State) {
+        self.value = state;
+    }
+
+    fn has_changed(&self) -> bool {
+        self.initial != self.value()
+    }
+
+    fn view(&self, disabled: bool) -> Node<Self::Msg> {
+        let validator = |str: &str| self.validate(str);
+        let error = error(&validator, &self.value, self.untouched, self.optional);
+        let danger = error.is_some();
+
+        div![
+            C!["field"],
+            label![
+                C!["label"],
+                &self.label,
+                IF!(self.optional => i![C!["has-text-grey"], " - Optional"])
+            ],
+            div![
+                C!["field", "has-addons", IF!(!danger => "mb-2")],
+                div![
+                    C!["control"],
+                    span![C!["button", "is-static"], self.currency.symbol]
+                ],
+                div![
+                    C!["control", "is-expanded"],
+                    input![
+                        C!["input", "has-text-right", IF!(danger => "is-danger")],
+                        input_ev(Ev::Input, InputMsg::Value),
+                        ev(Ev::Blur, |_| InputMsg::Blur),
+                        attrs! {At::Value => &self.value, At::Placeholder => &self.placeholder},
+                        attrs! {"inputmode" => "decimal"},
+                        IF!(disabled => attrs! {At::Disabled => ""}),
+                    ],
+                ],
+            ],
+            p![C!["help", "is-danger"], error.unwrap_or_default()],
+        ]
+    }
+
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn display_value(&self) -> String {
+        self.text(self.value())
+    }
+
+    fn display_initial(&self) -> String {
+        self.text(self.initial)
+    }
+
+    fn query(&self) -> Option<String> {
+        self.value().map(|_| self.value.clone())
+    }
+
+    fn set_query(&mut self, query: &str) {
+        if self.validate(query).is_ok() {
+            self.value = query.to_owned();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(str: &str, format: NumberFormat) -> Result<i64, String> {
+        Money::parse(str, Currency::EUR, format).map(|x| x.minor)
+    }
+
+    #[test]
+    fn parses_both_formats() {
+        assert_eq!(parse("1,234.56", NumberFormat::DOT), Ok(123456));
+        assert_eq!(parse("1.234,56", NumberFormat::COMMA), Ok(123456));
+        assert_eq!(parse("12.5", NumberFormat::DOT), Ok(1250));
+        assert_eq!(parse("12,5", NumberFormat::COMMA), Ok(1250));
+        assert_eq!(parse("-0.05", NumberFormat::DOT), Ok(-5));
+        assert_eq!(parse("1 234", NumberFormat::DOT), Ok(123400));
+    }
+
+    #[test]
+    fn rejects_misplaced_group_separators() {
+        assert!(parse("12,50", NumberFormat::DOT).is_err());
+        assert!(parse("12.50", NumberFormat::COMMA).is_err());
+        assert!(parse("1,23,456", NumberFormat::DOT).is_err());
+        assert!(parse(",123", NumberFormat::DOT).is_err());
+        assert!(parse("1.5.0", NumberFormat::DOT).is_err());
+    }
+
+    #[test]
+    fn rejects_extra_decimals() {
+        assert!(parse("1.234", NumberFormat::DOT).is_err());
+        assert!(Money::parse("1.5", Currency::JPY, NumberFormat::DOT).is_err());
+    }
+
+    #[test]
+    fn round_trips() {
+        for &format in &[NumberFormat::DOT, NumberFormat::COMMA] {
+            for &minor in &[0, 5, -5, 123456, -1234567890, 100000] {
+                let money = Money::new(minor, Currency::EUR);
+                assert_eq!(
+                    Money::parse(&money.format(format), Currency::EUR, format),
+                    Ok(money)
+                );
+            }
+            let yen = Money::new(1234567, Currency::JPY);
+            assert_eq!(
+                Money::parse(&yen.format(format), Currency::JPY, format),
+                Ok(yen)
+            );
+        }
+        assert_eq!(
+            Money::new(123456, Currency::EUR).format(NumberFormat::COMMA),
+            "1.234,56"
+        );
+    }
+}
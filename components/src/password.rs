@@ -0,0 +1,412 @@
+use seed::{prelude::*, *};
+
+use crate::field::{Field, EMPTY};
+use crate::input::{error, Validator};
+
+pub static MISMATCH: &str = "Passwords do not match.";
+
+static COMMON: &[&str] = &[
+    "123456",
+    "123456789",
+    "12345678",
+    "12345",
+    "1234567",
+    "1234567890",
+    "111111",
+    "000000",
+    "123123",
+    "654321",
+    "password",
+    "password1",
+    "passw0rd",
+    "qwerty",
+    "qwerty123",
+    "qwertyuiop",
+    "azerty",
+    "abc123",
+    "iloveyou",
+    "admin",
+    "welcome",
+    "letmein",
+    "monkey",
+    "dragon",
+    "football",
+    "baseball",
+    "sunshine",
+    "princess",
+    "master",
+    "login",
+    "starwars",
+    "whatever",
+    "trustno1",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum Strength {
+    VeryWeak,
+    Weak,
+    Fair,
+    Strong,
+    VeryStrong,
+}
+
+impl Strength {
+    pub fn of(password: &str) -> Self {
+        if password.is_empty() || COMMON.contains(&password.to_lowercase().as_str()) {
+            return Strength::VeryWeak;
+        }
+
+        let classes: [fn(&char) -> bool; 4] = [
+            char::is_ascii_lowercase,
+            char::is_ascii_uppercase,
+            char::is_ascii_digit,
+            |c| !c.is_alphanumeric(),
+        ];
+        let variety = classes
+            .iter()
+            .filter(|class| password.chars().any(|c| class(&c)))
+            .count();
+
+        let length = password.chars().count();
+        let score =
+            [8, 12, 16].iter().filter(|&&x| length >= x).count() + variety.saturating_sub(1);
+
+        match score {
+            0 | 1 => Strength::VeryWeak,
+            2 => Strength::Weak,
+            3 => Strength::Fair,
+            4 | 5 => Strength::Strong,
+            _ => Strength::VeryStrong,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Strength::VeryWeak => "Very weak",
+            Strength::Weak => "Weak",
+            Strength::Fair => "Fair",
+            Strength::Strong => "Strong",
+            Strength::VeryStrong => "Very strong",
+        }
+    }
+
+    fn style(&self) -> &'static str {
+        match self {
+            Strength::VeryWeak | Strength::Weak => "is-danger",
+            Strength::Fair => "is-warning",
+            Strength::Strong | Strength::VeryStrong => "is-success",
+        }
+    }
+}
+
+pub enum PasswordMsg {
+    Value(String),
+    Confirm(String),
+    Reveal,
+    Blur,
+}
+
+pub struct PasswordField {
+    label: String,
+    placeholder: String,
+
+    value: String,
+    confirm: Option<(String, String)>,
+
+    validator: Validator<String>,
+    min_strength: Option<Strength>,
+
+    meter: bool,
+    revealed: bool,
+
+    untouched: bool,
+    optional: bool,
+}
+
+impl PasswordField {
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            placeholder: String::new(),
+            value: String::new(),
+            confirm: None,
+            validator: Box::new(|x| Ok(x.to_owned())),
+            min_strength: None,
+            meter: false,
+            revealed: false,
+            untouched: true,
+            optional: false,
+        }
+    }
+
+    pub fn validator(
+        mut self,
+        validator: impl Fn(&str) -> Result<String, String> + 'static,
+    ) -> Self {
+        self.validator = Box::new(validator);
+        self
+    }
+
+    /// Rejects passwords weaker than the given strength, on top of the validator.
+    pub fn min_strength(mut self, min: Strength) -> Self {
+        self.min_strength = Some(min);
+        self.meter()
+    }
+
+    /// Shows a strength bar below the input.
+    pub fn meter(mut self) -> Self {
+        self.meter = true;
+        self
+    }
+
+    /// Adds a second input which must repeat the password.
+    pub fn confirm(mut self, label: impl Into<String>) -> Self {
+        self.confirm = Some((label.into(), String::new()));
+        self
+    }
+
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    fn check(&self, password: &str) -> Result<String, String> {
+        let password = (self.validator)(password)?;
+        match self.min_strength {
+            Some(min) if Strength::of(&password) < min => Err(format!(
+                "Password must be at least {}.",
+                min.name().to_lowercase()
+            )),
+            _ => Ok(password),
+        }
+    }
+
+    fn matches(&self) -> bool {
+        match &self.confirm {
+            Some((_, confirm)) => confirm == &self.value,
+            None => true,
+        }
+    }
+
+    /// Why the confirmation doesn't accept the password, once the password was typed.
+    fn confirm_error(&self) -> Option<&'static str> {
+        match &self.confirm {
+            Some(_) if self.untouched || self.value.is_empty() => None,
+            Some((_, confirm)) if confirm.is_empty() => Some(EMPTY),
+            Some(_) if !self.matches() => Some(MISMATCH),
+            _ => None,
+        }
+    }
+
+    fn view_input(
+        &self,
+        value: &str,
+        danger: bool,
+        disabled: bool,
+        confirm: bool,
+    ) -> Node<PasswordMsg> {
+        let kind = match self.revealed {
+            true => "text",
+            false => "password",
+        };
+        let eye = match self.revealed {
+            true => "fa-eye-slash",
+            false => "fa-eye",
+        };
+
+        div![
+            C!["field", "has-addons", "mb-0"],
+            div![
+                C!["control", "is-expanded"],
+                input![
+                    C!["input", IF!(danger => "is-danger")],
+                    match confirm {
+                        true => input_ev(Ev::Input, PasswordMsg::Confirm),
+                        false => input_ev(Ev::Input, PasswordMsg::Value),
+                    },
+                    ev(Ev::Blur, |_| PasswordMsg::Blur),
+                    attrs! {At::Type => kind, At::Value => value, At::Placeholder => &self.placeholder},
+                    IF!(disabled => attrs! {At::Disabled => ""}),
+                ],
+            ],
+            div![
+                C!["control"],
+                button![
+                    C!["button"],
+                    attrs! {At::Type => "button", At::Disabled => disabled.as_at_value()},
+                    ev(Ev::Click, |_| PasswordMsg::Reveal),
+                    span![C!["icon", "is-small"], i![C!["fas", eye]]]
+                ]
+            ]
+        ]
+    }
+
+    fn view_meter(&self) -> Node<PasswordMsg> {
+        let strength = Strength::of(&self.value);
+        div![
+            C!["mt-2"],
+            progress![
+                C!["progress", "is-small", "mb-1", strength.style()],
+                attrs! {At::Value => strength as usize + 1, At::Max => 5},
+            ],
+            p![C!["help"], strength.name()]
+        ]
+    }
+}
+
+impl Field for PasswordField {
+    type Msg = PasswordMsg;
+    type Value = Option<String>;
+    type State = ();
+
+    fn update(&mut self, msg: Self::Msg, _: &mut impl Orders<Self::Msg>) -> bool {
+        match msg {
+            PasswordMsg::Value(str) => {
+                self.untouched = false;
+                self.value = str;
+            }
+            PasswordMsg::Confirm(str) => {
+                if let Some((_, confirm)) = &mut self.confirm {
+                    *confirm = str;
+                }
+            }
+            PasswordMsg::Reveal => self.revealed = !self.revealed,
+            PasswordMsg::Blur => return false,
+        }
+        true
+    }
+
+    fn reset(&mut self) {
+        self.value.clear();
+        self.revealed = false;
+        if let Some((_, confirm)) = &mut self.confirm {
+            confirm.clear();
+        }
+    }
+
+    fn value(&self) -> Self::Value {
+        match self.value.is_empty() || !self.matches() {
+            true => None,
+            false => self.check(&self.value).ok(),
+        }
+    }
+
+    fn set_value(&mut self, value: Self::Value) {
+        self.value = value.unwrap_or_default();
+        if let Some((_, confirm)) = &mut self.confirm {
+            *confirm = self.value.clone();
+        }
+    }
+
+    fn state(&self) -> Self::State {}
+
+    fn set_state(&mut self, _: Self::State) {}
+
+    fn has_changed(&self) -> bool {
+        !self.value.is_empty()
+    }
+
+    fn view(&self, disabled: bool) -> Node<Self::Msg> {
+        let check = |x: &str| self.check(x);
+        let error = error(&check, &self.value, self.untouched, self.optional);
+        let danger = error.is_some();
+
+        let confirm = self.confirm.as_ref().map(|(label, value)| {
+            let error = self.confirm_error();
+            div![
+                C!["field", "mt-3"],
+                label![C!["label"], label],
+                self.view_input(value, error.is_some(), disabled, true),
+                p![C!["help", "is-danger"], error.unwrap_or_default()],
+            ]
+        });
+
+        div![
+            C!["field"],
+            label![
+                C!["label"],
+                &self.label,
+                IF!(self.optional => i![C!["has-text-grey"], " - Optional"])
+            ],
+            self.view_input(&self.value, danger, disabled, false),
+            IF!(self.meter && !self.value.is_empty() => self.view_meter()),
+            p![C!["help", "is-danger"], error.unwrap_or_default()],
+            confirm,
+        ]
+    }
+
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn display_value(&self) -> String {
+        match self.value.is_empty() {
+            true => String::new(),
+            false => "••••••••".to_owned(),
+        }
+    }
+
+    fn display_initial(&self) -> String {
+        String::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn common_and_empty_passwords_are_very_weak() {
+        assert_eq!(Strength::of(""), Strength::VeryWeak);
+        assert_eq!(Strength::of("password"), Strength::VeryWeak);
+        assert_eq!(Strength::of("Qwerty123"), Strength::VeryWeak);
+    }
+
+    #[test]
+    fn length_and_variety_add_up() {
+        assert_eq!(Strength::of("abcdefg"), Strength::VeryWeak);
+        assert_eq!(Strength::of("abcdefgh"), Strength::VeryWeak);
+        assert_eq!(Strength::of("abcdefgH"), Strength::Weak);
+        assert_eq!(Strength::of("abcdefgH1"), Strength::Fair);
+        assert_eq!(Strength::of("abcdefgH1!"), Strength::Strong);
+        assert_eq!(Strength::of("abcdefghijkL1!"), Strength::Strong);
+        assert_eq!(Strength::of("abcdefghijklmnoP1!"), Strength::VeryStrong);
+    }
+
+    #[test]
+    fn validator_and_min_strength_both_apply() {
+        let field = PasswordField::new("password")
+            .validator(|x| match x.contains(' ') {
+                true => Err("No spaces.".to_owned()),
+                false => Ok(x.to_owned()),
+            })
+            .min_strength(Strength::Fair);
+
+        assert_eq!(field.check("abcdefgH1"), Ok("abcdefgH1".to_owned()));
+        assert!(field.check("abcdefgh").is_err());
+        assert_eq!(field.check("abcd efgH1"), Err("No spaces.".to_owned()));
+    }
+
+    #[test]
+    fn empty_confirmation_is_explained() {
+        let mut field = PasswordField::new("password").confirm("repeat");
+        assert_eq!(field.confirm_error(), None);
+
+        field.value = "abcdefgH1".to_owned();
+        field.untouched = false;
+        assert_eq!(field.value(), None);
+        assert_eq!(field.confirm_error(), Some(EMPTY));
+
+        field.confirm = Some(("repeat".to_owned(), "abcdefgH".to_owned()));
+        assert_eq!(field.confirm_error(), Some(MISMATCH));
+
+        field.confirm = Some(("repeat".to_owned(), "abcdefgH1".to_owned()));
+        assert_eq!(field.confirm_error(), None);
+        assert_eq!(field.value(), Some("abcdefgH1".to_owned()));
+    }
+}
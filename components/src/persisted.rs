@@ -0,0 +1,307 @@
+use seed::browser::web_storage::WebStorageError;
+use seed::{prelude::*, *};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::button::Button;
+use crate::form::{Form, FormViewOptions};
+
+/// Milliseconds in a day, for the expiry of drafts.
+const DAY: f64 = 24. * 60. * 60. * 1000.;
+
+pub enum PersistedMsg<M> {
+    Form(M),
+    /// Looks for a draft once the builder methods such as `expiry` have been applied.
+    Load,
+    Save,
+    Restore,
+    Discard,
+    Synced(Option<String>),
+}
+
+#[derive(Serialize, Deserialize)]
+struct Draft<S> {
+    saved: f64,
+    snapshot: S,
+}
+
+/// Autosaves the values of a form as a draft in local storage, so it survives crashed tabs.
+///
+/// A draft found on load is offered through `view_draft` instead of being applied directly.
+/// The form is restored from serialized snapshots of the raw field states, which derived
+/// forms support as long as those implement `Serialize` and `Deserialize`.
+/// Edits in other tabs with the same key are synced through storage events.
+/// Passwords and files have no state and are never written to storage.
+/// Storage errors, e.g. in private browsing, are logged as drafts are a best effort.
+pub struct Persisted<F: Form>
+where
+    F::Snapshot: Serialize + DeserializeOwned,
+{
+    form: F,
+    key: String,
+
+    draft: Option<Draft<F::Snapshot>>,
+    expiry: f64,
+    debounce: u32,
+
+    _save: Option<CmdHandle>,
+    _storage: StreamHandle,
+}
+
+/// Logs storage errors to the console instead of failing.
+fn logged<T>(result: Result<T, WebStorageError>) -> Option<T> {
+    result
+        .map_err(|err| error!("Could not access the draft in local storage:", err))
+        .ok()
+}
+
+impl<F: Form> Persisted<F>
+where
+    F::Snapshot: Serialize + DeserializeOwned,
+{
+    /// Drafts older than a week are ignored, see `expiry`.
+    pub fn new(
+        key: impl Into<String>,
+        form: F,
+        orders: &mut impl Orders<PersistedMsg<F::Msg>>,
+    ) -> Self {
+        let key = key.into();
+        let storage_key = key.clone();
+        let synced = move |event: web_sys::Event| {
+            let event = event.unchecked_into::<web_sys::StorageEvent>();
+            match event.key() {
+                Some(key) if key != storage_key => None,
+                _ => Some(PersistedMsg::<F::Msg>::Synced(event.new_value())),
+            }
+        };
+
+        orders.send_msg(PersistedMsg::Load);
+
+        Self {
+            form,
+            key,
+            draft: None,
+            expiry: 7. * DAY,
+            debounce: 500,
+            _save: None,
+            _storage: orders.stream_with_handle(streams::window_event(Ev::Storage, synced)),
+        }
+    }
+
+    /// Ignores drafts saved more than the given number of days ago and removes them.
+    pub fn expiry(mut self, days: u32) -> Self {
+        self.expiry = f64::from(days) * DAY;
+        self
+    }
+
+    /// The time in milliseconds to wait after the last edit before saving.
+    pub fn debounce(mut self, debounce: u32) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    pub fn form(&self) -> &F {
+        &self.form
+    }
+
+    pub fn has_draft(&self) -> bool {
+        self.draft().is_some()
+    }
+
+    /// Removes the saved draft, call this once the form is submitted successfully.
+    pub fn clear(&mut self) {
+        self._save = None;
+        self.draft = None;
+        logged(LocalStorage::remove(&self.key));
+    }
+
+    fn draft(&self) -> Option<&Draft<F::Snapshot>> {
+        self.draft
+            .as_ref()
+            .filter(|draft| js_sys::Date::now() - draft.saved < self.expiry)
+    }
+
+    fn load(&mut self) {
+        let draft = match LocalStorage::get::<Draft<F::Snapshot>>(&self.key) {
+            Err(WebStorageError::KeyNotFoundError) => None,
+            result => logged(result),
+        };
+        let draft = match draft {
+            Some(draft) if js_sys::Date::now() - draft.saved >= self.expiry => {
+                logged(LocalStorage::remove(&self.key));
+                None
+            }
+            draft => draft,
+        };
+        self.draft = draft.filter(|draft| draft.snapshot != self.form.snapshot());
+    }
+
+    fn save(&mut self) {
+        self._save = None;
+        if !self.form.has_changed() {
+            logged(LocalStorage::remove(&self.key));
+            return;
+        }
+
+        let draft = Draft {
+            saved: js_sys::Date::now(),
+            snapshot: self.form.snapshot(),
+        };
+        logged(LocalStorage::insert(&self.key, &draft));
+    }
+
+    /// Offers to restore the draft found on load, renders nothing otherwise.
+    pub fn view_draft(&self) -> Node<PersistedMsg<F::Msg>> {
+        let draft = match self.draft() {
+            Some(draft) => draft,
+            None => return empty![],
+        };
+        let saved = js_sys::Date::new(&JsValue::from_f64(draft.saved))
+            .to_locale_string("default", &JsValue::UNDEFINED);
+
+        div![
+            C!["notification", "is-info", "is-light"],
+            p![
+                C!["mb-2"],
+                format!("Restore the draft saved on {}?", String::from(saved))
+            ],
+            div![
+                C!["buttons"],
+                Button::custom("restore", "is-info", "fa-undo", || PersistedMsg::Restore).shown(),
+                Button::delete(|| PersistedMsg::Discard).shown(),
+            ]
+        ]
+    }
+}
+
+impl<F: Form> Form for Persisted<F>
+where
+    F::Snapshot: Serialize + DeserializeOwned,
+{
+    type Msg = PersistedMsg<F::Msg>;
+    type Value = F::Value;
+    type Snapshot = F::Snapshot;
+    type Patch = F::Patch;
+
+    fn update(&mut self, msg: Self::Msg, orders: &mut impl Orders<Self::Msg>) -> bool {
+        match msg {
+            PersistedMsg::Form(msg) => {
+                let changed = self.form.update(msg, &mut orders.proxy(PersistedMsg::Form));
+                if changed {
+                    let save = cmds::timeout(self.debounce, || Self::Msg::Save);
+                    self._save = Some(orders.perform_cmd_with_handle(save));
+                }
+                changed
+            }
+            PersistedMsg::Load => {
+                self.load();
+                self.draft.is_some()
+            }
+            PersistedMsg::Save => {
+                self.save();
+                false
+            }
+            PersistedMsg::Restore => {
+                let fresh = self.has_draft();
+                match self.draft.take() {
+                    Some(draft) if fresh => {
+                        self.form.restore(draft.snapshot);
+                        true
+                    }
+                    _ => false,
+                }
+            }
+            PersistedMsg::Discard => {
+                self.draft = None;
+                self.save();
+                true
+            }
+            PersistedMsg::Synced(Some(json)) => {
+                match serde_json::from_str::<Draft<F::Snapshot>>(&json) {
+                    Ok(draft) => {
+                        self.draft = None;
+                        self.form.restore(draft.snapshot);
+                        true
+                    }
+                    Err(err) => {
+                        error!("Could not read the draft synced from another tab:", err);
+                        false
+                    }
+                }
+            }
+            PersistedMsg::Synced(None) => self.draft.take().is_some(),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.form.reset();
+        self.save();
+    }
+
+    fn value(&self) -> Self::Value {
+        self.form.value()
+    }
+
+    fn snapshot(&self) -> Self::Snapshot {
+        self.form.snapshot()
+    }
+
+    fn restore(&mut self, snapshot: Self::Snapshot) {
+        self.form.restore(snapshot);
+        self.save();
+    }
+
+    fn changes(&self) -> Self::Patch {
+        self.form.changes()
+    }
+
+    fn view_with(&self, options: FormViewOptions) -> Vec<Node<Self::Msg>> {
+        self.form
+            .view_with(options)
+            .into_iter()
+            .map(|node| node.map_msg(PersistedMsg::Form))
+            .collect()
+    }
+
+    fn view_summary(&self) -> Node<Self::Msg> {
+        self.form.view_summary().map_msg(PersistedMsg::Form)
+    }
+
+    fn names(&self) -> Vec<String> {
+        self.form.names()
+    }
+
+    fn query(&self) -> Vec<(String, String)> {
+        self.form.query()
+    }
+
+    fn restore_query(&mut self, search: &UrlSearch) {
+        self.form.restore_query(search);
+        self.save();
+    }
+
+    fn has_changed(&self) -> bool {
+        self.form.has_changed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::field::Field;
+    use crate::form::Form;
+    use crate::input::InputField;
+    use crate::password::PasswordField;
+
+    #[test]
+    fn drafts_leave_out_passwords() {
+        let mut form = (
+            InputField::new("user", |x| Ok(x.to_owned())),
+            PasswordField::new("password"),
+        );
+        form.0.set_value(Some("ada".to_owned()));
+        form.1.set_value(Some("hunter22".to_owned()));
+
+        let json = serde_json::to_string(&form.snapshot()).unwrap();
+        assert!(json.contains("ada"));
+        assert!(!json.contains("hunter22"));
+    }
+}
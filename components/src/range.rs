@@ -0,0 +1,217 @@
+use crate::field::Field;
+use crate::slider::{thumb_offset, Track};
+use crate::stylesheet;
+use seed::{prelude::*, *};
+
+static CSS: &str = include_str!("range.css");
+
+pub enum RangeSliderMsg {
+    Lower(f64),
+    Upper(f64),
+}
+
+pub struct RangeSliderField {
+    label: String,
+    track: Track,
+
+    value: (f64, f64),
+    initial: (f64, f64),
+}
+
+impl RangeSliderField {
+    /// Creates a range slider where `initial` is given in slider space, like `SliderField::new`.
+    pub fn new(
+        label: impl Into<String>,
+        initial: (f64, f64),
+        mapper: impl Fn(f64) -> f64 + 'static,
+    ) -> Self {
+        Self {
+            label: label.into(),
+            track: Track::new(mapper),
+            value: initial,
+            initial,
+        }
+    }
+
+    pub fn linear(label: impl Into<String>, initial: (f64, f64)) -> Self {
+        Self::new(label, initial, |x| x).inverse(|x| x)
+    }
+
+    pub fn inverse(mut self, inverse: impl Fn(f64) -> f64 + 'static) -> Self {
+        self.track.inverse = Some(Box::new(inverse));
+        self
+    }
+
+    pub fn initial(mut self, lower: f64, upper: f64) -> Self {
+        self.initial = (self.track.unmap(lower), self.track.unmap(upper));
+        self.value = self.initial;
+        self
+    }
+
+    pub fn min(mut self, min: f64) -> Self {
+        self.track.min = min;
+        self
+    }
+
+    pub fn max(mut self, max: f64) -> Self {
+        self.track.max = max;
+        self
+    }
+
+    pub fn step(mut self, step: f64) -> Self {
+        self.track.step = step;
+        self
+    }
+
+    fn clamp(&self, (lower, upper): (f64, f64)) -> (f64, f64) {
+        (self.track.clamp(lower), self.track.clamp(upper))
+    }
+
+    /// The position after moving a thumb, which stops at the other one instead of crossing it.
+    fn moved(&self, msg: RangeSliderMsg) -> (f64, f64) {
+        let (lower, upper) = self.clamp(self.value);
+        match msg {
+            RangeSliderMsg::Lower(x) => (x.min(upper), upper),
+            RangeSliderMsg::Upper(x) => (lower, x.max(lower)),
+        }
+    }
+
+    fn text(&self, value: (f64, f64)) -> String {
+        let (lower, upper) = self.clamp(value);
+        format!(
+            "{:.2} – {:.2}",
+            self.track.map(lower),
+            self.track.map(upper)
+        )
+    }
+
+    fn view_thumb(
+        &self,
+        value: f64,
+        msg: fn(f64) -> RangeSliderMsg,
+        disabled: bool,
+    ) -> Vec<Node<RangeSliderMsg>> {
+        vec![
+            self.track.view_input(value, msg, disabled),
+            p![
+                thumb_offset(self.track.fraction(value)),
+                style! {St::Top => "15px"},
+                format!("{:.2}", self.track.map(value))
+            ],
+        ]
+    }
+}
+
+impl Field for RangeSliderField {
+    type Msg = RangeSliderMsg;
+    type Value = (f64, f64);
+    type State = (f64, f64);
+
+    fn update(&mut self, msg: Self::Msg, _: &mut impl Orders<Self::Msg>) -> bool {
+        let value = self.moved(msg);
+        std::mem::replace(&mut self.value, value) != value
+    }
+
+    fn reset(&mut self) {
+        self.value = self.initial
+    }
+
+    fn value(&self) -> Self::Value {
+        let (lower, upper) = self.clamp(self.value);
+        (self.track.map(lower), self.track.map(upper))
+    }
+
+    fn set_value(&mut self, (lower, upper): Self::Value) {
+        let (lower, upper) = (self.track.unmap(lower), self.track.unmap(upper));
+        self.value = (lower.min(upper), upper.max(lower));
+    }
+
+    /// The positions of the handles in slider space, which need no inverse to restore.
+    fn state(&self) -> Self::State {
+        self.value
+    }
+
+    fn set_state(&mut self, state: Self::State) {
+        self.value = state;
+    }
+
+    fn has_changed(&self) -> bool {
+        self.clamp(self.value) != self.clamp(self.initial)
+    }
+
+    fn view(&self, disabled: bool) -> Node<Self::Msg> {
+        stylesheet::inject("range-slider-css", CSS);
+
+        let (lower, upper) = self.clamp(self.value);
+        div![
+            C!["field"],
+            label![C!["label"], &self.label],
+            div![
+                C!["control", "range-slider", "pb-5"],
+                self.view_thumb(lower, RangeSliderMsg::Lower, disabled),
+                self.view_thumb(upper, RangeSliderMsg::Upper, disabled),
+            ]
+        ]
+    }
+
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn display_value(&self) -> String {
+        self.text(self.value)
+    }
+
+    fn display_initial(&self) -> String {
+        self.text(self.initial)
+    }
+
+    /// Both bounds separated by a comma, e.g. `0.2,0.8`.
+    fn query(&self) -> Option<String> {
+        let (lower, upper) = self.value();
+        Some(format!("{},{}", lower, upper))
+    }
+
+    fn set_query(&mut self, query: &str) {
+        let mut bounds = query.split(',').map(str::parse);
+        if let (Some(Ok(lower)), Some(Ok(upper)), None) =
+            (bounds.next(), bounds.next(), bounds.next())
+        {
+            self.set_value((lower, upper));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn thumbs_stop_at_each_other() {
+        let slider = RangeSliderField::linear("x", (0.3, 0.6));
+        assert_eq!(slider.moved(RangeSliderMsg::Lower(0.8)), (0.6, 0.6));
+        assert_eq!(slider.moved(RangeSliderMsg::Lower(0.1)), (0.1, 0.6));
+        assert_eq!(slider.moved(RangeSliderMsg::Upper(0.2)), (0.3, 0.3));
+        assert_eq!(slider.moved(RangeSliderMsg::Upper(0.9)), (0.3, 0.9));
+    }
+
+    #[test]
+    fn thumbs_stop_at_the_clamped_other_thumb() {
+        let slider = RangeSliderField::linear("x", (0.5, 20.)).max(10.);
+        assert_eq!(slider.moved(RangeSliderMsg::Lower(15.)), (10., 10.));
+    }
+
+    #[test]
+    fn set_value_orders_the_bounds() {
+        let mut slider = RangeSliderField::linear("x", (0., 1.));
+        slider.set_value((0.8, 0.2));
+        assert_eq!(slider.value(), (0.2, 0.8));
+    }
+
+    #[test]
+    fn values_are_clamped_when_read() {
+        let slider = RangeSliderField::linear("x", (0., 1.)).initial(-1., 5.);
+        assert_eq!(slider.value(), (0., 1.));
+        assert!(!slider.has_changed());
+    }
+}
@@ -2,7 +2,7 @@ use seed::{prelude::*, *};
 
 use crate::Field;
 
-pub enum Msg {
+pub enum SelectMsg {
     Value(i64),
     Blur,
 }
@@ -45,6 +45,15 @@ impl SelectField {
         self
     }
 
+    fn name(&self, id: Option<i64>) -> String {
+        self.default
+            .iter()
+            .chain(self.suggestions.iter())
+            .find(|x| Some(x.0) == id)
+            .map(|x| x.1.clone())
+            .unwrap_or_default()
+    }
+
     fn view_option(&self, suggestion: &(i64, String)) -> Node<<Self as Field>::Msg> {
         let selected = self.initial == Some(suggestion.0);
         option![
@@ -63,15 +72,16 @@ impl SelectField {
 }
 
 impl Field for SelectField {
-    type Msg = Msg;
+    type Msg = SelectMsg;
     type Value = Option<i64>;
+    type State = Option<i64>;
 
     fn update(&mut self, msg: Self::Msg, _: &mut impl Orders<Self::Msg>) -> bool {
         self.untouched = false;
 
         self.value = match msg {
-            Msg::Value(value) => self.suggestions.iter().map(|x| x.0).find(|&x| x == value),
-            Msg::Blur => return false,
+            SelectMsg::Value(value) => self.suggestions.iter().map(|x| x.0).find(|&x| x == value),
+            SelectMsg::Blur => return false,
         };
         true
     }
@@ -88,6 +98,18 @@ impl Field for SelectField {
         self.value
     }
 
+    fn set_value(&mut self, value: Self::Value) {
+        self.value = value.filter(|&x| self.suggestions.iter().any(|y| y.0 == x));
+    }
+
+    fn state(&self) -> Self::State {
+        self.value
+    }
+
+    fn set_state(&mut self, state: Self::State) {
+        self.value = state;
+    }
+
     fn has_changed(&self) -> bool {
         self.initial != self.value
     }
@@ -105,12 +127,37 @@ impl Field for SelectField {
                     ],
                     select![
                         IF!(readonly => attrs! {At::Disabled => ""}),
-                        input_ev(Ev::Input, |str| str.parse::<i64>().ok().map(Msg::Value)),
-                        ev(Ev::Blur, |_| Msg::Blur),
+                        input_ev(Ev::Input, |str| str
+                            .parse::<i64>()
+                            .ok()
+                            .map(SelectMsg::Value)),
+                        ev(Ev::Blur, |_| SelectMsg::Blur),
                         self.view_options()
                     ]
                 ]
             ]
         ]
     }
+
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn display_value(&self) -> String {
+        self.name(self.value)
+    }
+
+    fn display_initial(&self) -> String {
+        self.name(self.initial)
+    }
+
+    fn query(&self) -> Option<String> {
+        self.value.map(|x| x.to_string())
+    }
+
+    fn set_query(&mut self, query: &str) {
+        if let Ok(id) = query.parse() {
+            self.set_value(Some(id));
+        }
+    }
 }
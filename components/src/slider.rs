@@ -1,16 +1,102 @@
 use crate::field::Field;
 use seed::{prelude::*, *};
 
+/// Width of the slider thumb in pixels, must match the `.slider` thumb in the stylesheet.
+pub(crate) const THUMB: f64 = 25.;
+
+pub type Mapper = Box<dyn Fn(f64) -> f64>;
+
+pub enum SliderMsg {
+    Value(f64),
+    Text(String),
+    Blur,
+}
+
+/// Centers an absolutely positioned node on the thumb at the given fraction of the track.
+pub(crate) fn thumb_offset(fraction: f64) -> Style {
+    style! {
+        St::Position => "absolute",
+        St::Transform => "translateX(-50%)",
+        St::WhiteSpace => "nowrap",
+        St::Left => format!("calc({}% + {}px)", 100. * fraction, (0.5 - fraction) * THUMB),
+    }
+}
+
+/// The bounds and step of a slider in slider space, and the mapping onto real values.
+/// Shared by `SliderField` and `RangeSliderField`.
+pub(crate) struct Track {
+    pub mapper: Mapper,
+    pub inverse: Option<Mapper>,
+
+    pub min: f64,
+    pub max: f64,
+    pub step: f64,
+}
+
+impl Track {
+    pub fn new(mapper: impl Fn(f64) -> f64 + 'static) -> Self {
+        Self {
+            mapper: Box::new(mapper),
+            inverse: None,
+            min: 0.,
+            max: 1.,
+            step: 0.01,
+        }
+    }
+
+    pub fn map(&self, value: f64) -> f64 {
+        (self.mapper)(value)
+    }
+
+    pub fn unmap(&self, value: f64) -> f64 {
+        match &self.inverse {
+            Some(inverse) => inverse(value),
+            None => value,
+        }
+    }
+
+    /// Values are only clamped when read, so the order of the builder calls doesn't matter.
+    pub fn clamp(&self, value: f64) -> f64 {
+        value.max(self.min).min(self.max)
+    }
+
+    pub fn fraction(&self, value: f64) -> f64 {
+        (value - self.min) / (self.max - self.min)
+    }
+
+    /// The native range input with its thumb at the given position in slider space.
+    pub fn view_input<Ms: 'static>(
+        &self,
+        value: f64,
+        msg: fn(f64) -> Ms,
+        disabled: bool,
+    ) -> Node<Ms> {
+        input![
+            C!["slider"],
+            attrs! { At::Min => self.min, At::Max => self.max, At::Step => self.step },
+            attrs! { At::Type => "range", At::Value => value },
+            IF!(disabled => attrs! {At::Disabled => ""}),
+            input_ev(Ev::Input, move |str| str.parse::<f64>().ok().map(msg)),
+        ]
+    }
+}
+
 pub struct SliderField {
     label: String,
+    track: Track,
 
-    mapper: Box<dyn Fn(f64) -> f64>,
+    ticks: Vec<f64>,
+    snaps: Vec<f64>,
+
+    editable: bool,
+    text: Option<String>,
 
     value: f64,
     initial: f64,
 }
 
 impl SliderField {
+    /// Creates a slider where `initial` is given in slider space, see `inverse` to use real values.
     pub fn new(
         label: impl Into<String>,
         initial: f64,
@@ -18,58 +104,351 @@ impl SliderField {
     ) -> Self {
         Self {
             label: label.into(),
-            mapper: Box::new(mapper),
+            track: Track::new(mapper),
+            ticks: Vec::new(),
+            snaps: Vec::new(),
+            editable: false,
+            text: None,
             value: initial,
             initial,
         }
     }
 
     pub fn linear(label: impl Into<String>, initial: f64) -> Self {
-        Self::new(label, initial, |x| x)
+        Self::new(label, initial, |x| x).inverse(|x| x)
+    }
+
+    /// Maps the slider onto `min..max` so that every step multiplies the value by the same factor.
+    ///
+    /// # Panics
+    ///
+    /// When `min` isn't positive or `max` isn't above `min`, as the scale would be undefined.
+    pub fn logarithmic(label: impl Into<String>, initial: f64, min: f64, max: f64) -> Self {
+        assert!(
+            min > 0. && max > min,
+            "a logarithmic slider needs 0 < min < max, got {}..{}",
+            min,
+            max
+        );
+        let ratio = max / min;
+        Self::new(label, 0., move |x| min * ratio.powf(x))
+            .inverse(move |x| (x / min).ln() / ratio.ln())
+            .initial(initial)
+    }
+
+    /// Maps the slider onto `min..max` through a power curve, giving finer control near `min`.
+    ///
+    /// # Panics
+    ///
+    /// When `exponent` isn't positive or `max` isn't above `min`, as the curve would be undefined.
+    pub fn exponential(
+        label: impl Into<String>,
+        initial: f64,
+        min: f64,
+        max: f64,
+        exponent: f64,
+    ) -> Self {
+        assert!(
+            exponent > 0. && max > min,
+            "an exponential slider needs min < max and a positive exponent, got {}..{} and {}",
+            min,
+            max,
+            exponent
+        );
+        Self::new(label, 0., move |x| min + (max - min) * x.powf(exponent))
+            .inverse(move |x| ((x - min) / (max - min)).powf(1. / exponent))
+            .initial(initial)
+    }
+
+    /// Converts real values back into slider space, `initial` and `set_value` use it when present.
+    pub fn inverse(mut self, inverse: impl Fn(f64) -> f64 + 'static) -> Self {
+        self.track.inverse = Some(Box::new(inverse));
+        self
+    }
+
+    pub fn initial(mut self, initial: f64) -> Self {
+        self.initial = self.track.unmap(initial);
+        self.value = self.initial;
+        self
+    }
+
+    pub fn min(mut self, min: f64) -> Self {
+        self.track.min = min;
+        self
+    }
+
+    pub fn max(mut self, max: f64) -> Self {
+        self.track.max = max;
+        self
+    }
+
+    pub fn step(mut self, step: f64) -> Self {
+        self.track.step = step;
+        self
+    }
+
+    /// Shows labelled tick marks below the slider at the given real values, needs an `inverse`.
+    pub fn ticks(mut self, ticks: Vec<f64>) -> Self {
+        self.ticks = ticks;
+        self
+    }
+
+    /// Restricts the slider to the given real values, snapping to the closest one.
+    /// Needs an `inverse` to place the values on the slider.
+    pub fn snap(mut self, values: Vec<f64>) -> Self {
+        self.snaps = values;
+        self
+    }
+
+    /// Adds a numeric input next to the slider for entering exact values, needs an `inverse`.
+    pub fn editable(mut self) -> Self {
+        self.editable = true;
+        self
+    }
+
+    fn snapped(&self, value: f64) -> f64 {
+        let distance = |x: &f64| (x - value).abs();
+        self.snaps
+            .iter()
+            .map(|&x| self.track.clamp(self.track.unmap(x)))
+            .min_by(|a, b| distance(a).partial_cmp(&distance(b)).unwrap())
+            .unwrap_or(value)
+    }
+
+    fn validate(&self, str: &str) -> Result<f64, String> {
+        let value = self.track.unmap(
+            str.trim()
+                .parse::<f64>()
+                .map_err(|_| "Must be valid number.")?,
+        );
+
+        match value >= self.track.min && value <= self.track.max {
+            true => Ok(self.snapped(value)),
+            false => Err(format!(
+                "Must be between {:.2} and {:.2}.",
+                self.track.map(self.track.min),
+                self.track.map(self.track.max)
+            )),
+        }
+    }
+
+    fn view_ticks(&self) -> Node<SliderMsg> {
+        let tick = |&x: &f64| {
+            let fraction = self.track.fraction(self.track.clamp(self.track.unmap(x)));
+            span![
+                C!["is-size-7", "has-text-grey"],
+                thumb_offset(fraction),
+                format!("{:.2}", x)
+            ]
+        };
+
+        div![
+            style! {St::Position => "relative", St::Height => "1.5em"},
+            self.ticks.iter().map(tick)
+        ]
+    }
+
+    fn view_text(&self, disabled: bool) -> Node<SliderMsg> {
+        let text = match &self.text {
+            Some(text) => text.clone(),
+            None => format!("{:.2}", self.value()),
+        };
+        let error = self.text.as_ref().and_then(|x| self.validate(x).err());
+
+        div![
+            C!["control"],
+            input![
+                C!["input", IF!(error.is_some() => "is-danger")],
+                style! {St::Width => "7em"},
+                attrs! {At::Type => "number", At::Value => text},
+                input_ev(Ev::Input, SliderMsg::Text),
+                ev(Ev::Blur, |_| SliderMsg::Blur),
+                IF!(disabled => attrs! {At::Disabled => ""}),
+            ],
+            p![C!["help", "is-danger"], error.unwrap_or_default()]
+        ]
     }
 }
 
 impl Field for SliderField {
-    type Msg = f64;
+    type Msg = SliderMsg;
     type Value = f64;
+    type State = f64;
 
     fn update(&mut self, msg: Self::Msg, _: &mut impl Orders<Self::Msg>) -> bool {
+        let value = match msg {
+            SliderMsg::Value(value) => {
+                self.text = None;
+                self.snapped(value)
+            }
+            SliderMsg::Text(text) => {
+                let value = self.validate(&text).unwrap_or(self.value);
+                self.text = Some(text);
+                value
+            }
+            SliderMsg::Blur => {
+                if let Some(Ok(_)) = self.text.as_ref().map(|x| self.validate(x)) {
+                    self.text = None;
+                }
+                return false;
+            }
+        };
+
         // Only returns true if a new value has been assigned
-        std::mem::replace(&mut self.value, msg) != msg
+        std::mem::replace(&mut self.value, value) != value
     }
 
     fn reset(&mut self) {
-        self.value = self.initial
+        self.value = self.initial;
+        self.text = None;
     }
 
     fn value(&self) -> Self::Value {
-        (self.mapper)(self.value)
+        self.track.map(self.track.clamp(self.value))
+    }
+
+    fn set_value(&mut self, value: Self::Value) {
+        self.value = self.snapped(self.track.unmap(value));
+        self.text = None;
+    }
+
+    /// The position in slider space, which needs no inverse to restore.
+    fn state(&self) -> Self::State {
+        self.value
+    }
+
+    fn set_state(&mut self, state: Self::State) {
+        self.value = state;
+        self.text = None;
     }
 
     fn has_changed(&self) -> bool {
-        self.value != self.initial
+        self.track.clamp(self.value) != self.track.clamp(self.initial)
     }
 
-    fn view(&self, _: bool) -> Node<Self::Msg> {
+    fn view(&self, disabled: bool) -> Node<Self::Msg> {
+        // Without an inverse real values would be used as positions on the slider
+        let real = self.editable || !self.ticks.is_empty() || !self.snaps.is_empty();
+        debug_assert!(
+            self.track.inverse.is_some() || !real,
+            "editable, snap and ticks take real values, call inverse on slider {:?}",
+            self.label
+        );
+
+        let value = self.track.clamp(self.value);
         div![
             C!["field"],
             label![C!["label"], &self.label],
             div![
-                C!["control pb-4"],
-                input![
-                    C!["slider"],
-                    attrs! { At::Min => 0, At::Max => 1, At::Step => 0.01 },
-                    attrs! { At::Type => "range", At::Value => self.value },
-                    input_ev(Ev::Input, |str| str.parse::<f64>().ok()),
+                C!["field", "is-grouped"],
+                div![
+                    C!["control", "is-expanded", "pb-4"],
+                    self.track.view_input(value, SliderMsg::Value, disabled),
+                    IF!(!self.editable => p![
+                        thumb_offset(self.track.fraction(value)),
+                        format!("{:.2}", self.value())
+                    ]),
+                    IF!(!self.ticks.is_empty() => self.view_ticks()),
                 ],
-                p![
-                    style! {
-                    St::Position => "absolute",
-                    St::TextAlign => "center",
-                    St::Left => format!("calc({}% - ({}px))", 100. * self.value, self.value * 25.)},
-                    format!("{:.2}", (self.mapper)(self.value))
-                ]
+                IF!(self.editable => self.view_text(disabled)),
             ]
         ]
     }
+
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn display_value(&self) -> String {
+        format!("{:.2}", self.value())
+    }
+
+    fn display_initial(&self) -> String {
+        format!("{:.2}", self.track.map(self.track.clamp(self.initial)))
+    }
+
+    fn query(&self) -> Option<String> {
+        Some(self.value().to_string())
+    }
+
+    fn set_query(&mut self, query: &str) {
+        if let Ok(value) = query.parse() {
+            self.set_value(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_order_does_not_clamp_initial() {
+        let slider = SliderField::linear("x", 0.).initial(50.).max(100.);
+        assert_eq!(slider.value(), 50.);
+
+        let slider = SliderField::linear("x", 0.).max(100.).initial(50.);
+        assert_eq!(slider.value(), 50.);
+        assert!(!slider.has_changed());
+    }
+
+    #[test]
+    fn out_of_range_values_are_clamped_when_read() {
+        let slider = SliderField::linear("x", 0.).initial(5.);
+        assert_eq!(slider.value(), 1.);
+    }
+
+    #[test]
+    fn logarithmic_round_trips() {
+        let slider = SliderField::logarithmic("x", 10., 1., 1000.);
+        assert!((slider.value() - 10.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn custom_mappers_restore_their_state() {
+        let mut slider = SliderField::new("x", 0., |x| x * x);
+        slider.set_state(0.5);
+        assert_eq!(slider.state(), 0.5);
+        assert_eq!(slider.value(), 0.25);
+    }
+
+    #[test]
+    #[should_panic]
+    fn logarithmic_rejects_non_positive_min() {
+        SliderField::logarithmic("x", 1., 0., 10.);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(debug_assertions)]
+    fn editable_needs_an_inverse() {
+        SliderField::new("x", 0., |x| x * x).editable().view(false);
+    }
+
+    #[test]
+    fn editable_takes_real_values() {
+        let slider = SliderField::new("x", 0., |x| x * x)
+            .inverse(f64::sqrt)
+            .editable();
+        slider.view(false);
+        assert_eq!(slider.validate("0.25"), Ok(0.5));
+    }
+
+    #[test]
+    fn exponential_round_trips() {
+        let slider = SliderField::exponential("x", 25., 0., 100., 2.);
+        assert!((slider.value() - 25.).abs() < 1e-9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn exponential_rejects_zero_exponent() {
+        SliderField::exponential("x", 1., 0., 10., 0.);
+    }
+
+    #[test]
+    #[should_panic]
+    fn exponential_rejects_empty_range() {
+        SliderField::exponential("x", 1., 10., 10., 2.);
+    }
 }
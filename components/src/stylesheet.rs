@@ -0,0 +1,20 @@
+use seed::document;
+
+/// Adds the css of a component to the page head, unless a previous instance already did.
+/// Components call this when rendering, so they can still be constructed outside the browser.
+pub(crate) fn inject(id: &str, css: &str) {
+    let document = document();
+    if document.get_element_by_id(id).is_some() {
+        return;
+    }
+
+    let head = match document.query_selector("head") {
+        Ok(Some(head)) => head,
+        _ => return,
+    };
+    if let Ok(style) = document.create_element("style") {
+        style.set_id(id);
+        style.set_text_content(Some(css));
+        let _ = head.append_child(&style);
+    }
+}
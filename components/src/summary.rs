@@ -0,0 +1,33 @@
+use seed::{prelude::*, *};
+
+use crate::field::Field;
+
+/// A table row with the label and value of a field, showing `old → new` when it has changed.
+pub fn view_summary_row<Msg>(field: &impl Field) -> Node<Msg> {
+    let text = |text: String| match text.is_empty() {
+        true => span![C!["has-text-grey"], "—"],
+        false => span![style! {St::WhiteSpace => "pre-wrap"}, text],
+    };
+
+    let changed = field.has_changed();
+    tr![
+        IF!(changed => C!["has-background-warning-light"]),
+        th![field.label()],
+        td![match changed {
+            true => vec![
+                del![C!["has-text-grey"], text(field.display_initial())],
+                span![C!["mx-2"], "→"],
+                strong![text(field.display_value())],
+            ],
+            false => vec![text(field.display_value())],
+        }]
+    ]
+}
+
+pub fn view_summary<Msg>(rows: Vec<Node<Msg>>) -> Node<Msg> {
+    table![
+        C!["table", "is-fullwidth", "is-bordered"],
+        thead![tr![th!["Field"], th!["Value"]]],
+        tbody![rows]
+    ]
+}
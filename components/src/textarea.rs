@@ -0,0 +1,198 @@
+use seed::{prelude::*, *};
+use std::fmt::Debug;
+
+use crate::field::Field;
+use crate::input::{error, InputMsg, Validator};
+
+pub struct TextAreaField<T: ToString + Clone + PartialEq + Debug> {
+    label: String,
+    placeholder: String,
+
+    value: String,
+    initial: Option<T>,
+
+    validator: Validator<T>,
+
+    rows: usize,
+    autogrow: bool,
+    maxlength: Option<usize>,
+
+    untouched: bool,
+    optional: bool,
+}
+
+impl<T: ToString + Clone + PartialEq + Debug> TextAreaField<T> {
+    pub fn new(
+        label: impl Into<String>,
+        validator: impl Fn(&str) -> Result<T, String> + 'static,
+    ) -> Self {
+        Self {
+            label: label.into(),
+            placeholder: String::new(),
+            value: String::new(),
+            initial: None,
+            validator: Box::new(validator),
+            rows: 3,
+            autogrow: false,
+            maxlength: None,
+            untouched: true,
+            optional: false,
+        }
+    }
+
+    pub fn initial(mut self, initial: T) -> Self {
+        self.value = initial.to_string();
+        self.initial = Some(initial);
+        self
+    }
+
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    pub fn rows(mut self, rows: usize) -> Self {
+        self.rows = rows;
+        self
+    }
+
+    /// Grows the textarea with its content, the configured rows act as the minimum height.
+    pub fn autogrow(mut self) -> Self {
+        self.autogrow = true;
+        self
+    }
+
+    pub fn maxlength(mut self, maxlength: usize) -> Self {
+        self.maxlength = Some(maxlength);
+        self
+    }
+
+    fn visible_rows(&self) -> usize {
+        match self.autogrow {
+            true => self.rows.max(self.value.lines().count() + 1),
+            false => self.rows,
+        }
+    }
+}
+
+impl TextAreaField<String> {
+    pub fn string(label: impl Into<String>) -> Self {
+        Self::new(label, |x| Ok(x.to_owned()))
+    }
+}
+
+impl<T: ToString + Clone + PartialEq + Debug> Field for TextAreaField<T> {
+    type Msg = InputMsg;
+    type Value = Option<T>;
+    type State = String;
+
+    fn update(&mut self, msg: Self::Msg, _: &mut impl Orders<Self::Msg>) -> bool {
+        self.untouched = false;
+
+        self.value = match msg {
+            InputMsg::Value(str) => str,
+            InputMsg::Blur | InputMsg::Submit => return false,
+        };
+        true
+    }
+
+    fn reset(&mut self) {
+        self.value = match &self.initial {
+            Some(x) => x.to_string(),
+            None => String::new(),
+        }
+    }
+
+    fn value(&self) -> Self::Value {
+        match self.value.is_empty() {
+            true => None,
+            false => (self.validator)(&self.value).ok(),
+        }
+    }
+
+    fn set_value(&mut self, value: Self::Value) {
+        self.value = value.map(|x| x.to_string()).unwrap_or_default();
+    }
+
+    fn state(&self) -> Self::State {
+        self.value.clone()
+    }
+
+    fn set_state(&mut self, state: Self::State) {
+        self.value = state;
+    }
+
+    fn has_changed(&self) -> bool {
+        match &self.initial {
+            Some(x) => x.to_string() != self.value,
+            None => !self.value.is_empty(),
+        }
+    }
+
+    fn view(&self, disabled: bool) -> Node<Self::Msg> {
+        let error = error(&self.validator, &self.value, self.untouched, self.optional);
+        let danger = error.is_some();
+
+        let counter = self.maxlength.map(|max| {
+            p![
+                C!["help", "has-text-right", "is-pulled-right"],
+                format!("{}/{}", self.value.chars().count(), max)
+            ]
+        });
+
+        div![
+            C!["field"],
+            label![
+                C!["label"],
+                &self.label,
+                IF!(self.optional => i![C!["has-text-grey"], " - Optional"])
+            ],
+            div![
+                C!["control"],
+                IF!(!danger => C!["mb-2"]),
+                textarea![
+                    C![
+                        "textarea",
+                        IF!(danger => "is-danger"),
+                        IF!(self.autogrow => "has-fixed-size")
+                    ],
+                    input_ev(Ev::Input, InputMsg::Value),
+                    ev(Ev::Blur, |_| InputMsg::Blur),
+                    attrs! {At::Placeholder => &self.placeholder, At::Rows => self.visible_rows()},
+                    attrs! {At::Value => &self.value},
+                    self.maxlength.map(|max| attrs! {At::MaxLength => max}),
+                    IF!(disabled => attrs! {At::Disabled => ""}),
+                ],
+            ],
+            counter,
+            p![C!["help", "is-danger"], error.unwrap_or_default()],
+        ]
+    }
+
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn display_value(&self) -> String {
+        self.value.clone()
+    }
+
+    fn display_initial(&self) -> String {
+        self.initial.as_ref().map(T::to_string).unwrap_or_default()
+    }
+
+    fn query(&self) -> Option<String> {
+        self.value().map(|_| self.value.clone())
+    }
+
+    fn set_query(&mut self, query: &str) {
+        if (self.validator)(query).is_ok() {
+            self.value = query.to_owned();
+        }
+    }
+}
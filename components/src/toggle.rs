@@ -1,28 +1,139 @@
 use crate::field::Field;
+use crate::stylesheet;
 use seed::{prelude::*, *};
 
-pub struct ToggleField {
+static CSS: &str = include_str!("toggle.css");
+
+/// The states a toggle cycles through, `Option<bool>` adds an unset state for filters.
+pub trait ToggleState: Copy + PartialEq + 'static {
+    fn next(self) -> Self;
+    fn checked(self) -> bool;
+    fn indeterminate(self) -> bool;
+    fn from_checked(checked: bool) -> Self;
+}
+
+impl ToggleState for bool {
+    fn next(self) -> Self {
+        !self
+    }
+
+    fn checked(self) -> bool {
+        self
+    }
+
+    fn indeterminate(self) -> bool {
+        false
+    }
+
+    fn from_checked(checked: bool) -> Self {
+        checked
+    }
+}
+
+impl ToggleState for Option<bool> {
+    fn next(self) -> Self {
+        match self {
+            None => Some(true),
+            Some(true) => Some(false),
+            Some(false) => None,
+        }
+    }
+
+    fn checked(self) -> bool {
+        self == Some(true)
+    }
+
+    fn indeterminate(self) -> bool {
+        self.is_none()
+    }
+
+    fn from_checked(checked: bool) -> Self {
+        Some(checked)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Placement {
+    Left,
+    Right,
+    Top,
+}
+
+pub struct ToggleField<T: ToggleState = bool> {
     label: String,
-    value: bool,
-    initial: bool,
+    value: T,
+    initial: T,
+
+    checkbox: bool,
+    placement: Placement,
 }
 
-impl ToggleField {
-    pub fn new(label: impl Into<String>, initial: bool) -> Self {
+fn text(state: impl ToggleState) -> String {
+    let text = match (state.indeterminate(), state.checked()) {
+        (true, _) => "",
+        (false, true) => "Yes",
+        (false, false) => "No",
+    };
+    text.to_owned()
+}
+
+impl<T: ToggleState> ToggleField<T> {
+    pub fn new(label: impl Into<String>, initial: T) -> Self {
         Self {
             label: label.into(),
             value: initial,
             initial,
+            checkbox: false,
+            placement: Placement::Right,
         }
     }
+
+    /// Renders a compact checkbox instead of a switch.
+    pub fn checkbox(mut self) -> Self {
+        self.checkbox = true;
+        self
+    }
+
+    /// Where the label is shown relative to the switch or checkbox.
+    pub fn placement(mut self, placement: Placement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    fn view_toggle(&self, state: Node<()>) -> Node<()> {
+        div![
+            C![
+                "toggle",
+                IF!(self.value.indeterminate() => "is-indeterminate")
+            ],
+            state,
+            div![C!["toggle-inner"], div![C!["indicator"]]],
+            div![C!["active-bg"]],
+        ]
+    }
+
+    fn view_checkbox(&self, state: Node<()>) -> Node<()> {
+        let icon = match (self.value.indeterminate(), self.value.checked()) {
+            (true, _) => "fa-minus-square",
+            (false, true) => "fa-check-square",
+            (false, false) => "fa-square",
+        };
+        let weight = match self.value.checked() || self.value.indeterminate() {
+            true => "fas",
+            false => "far",
+        };
+
+        span![C!["icon"], state, i![C![weight, icon]]]
+    }
 }
 
-impl Field for ToggleField {
+impl<T: ToggleState> Field for ToggleField<T> {
     type Msg = ();
-    type Value = bool;
+    type Value = T;
+    type State = T;
 
     fn update(&mut self, _: Self::Msg, _: &mut impl Orders<Self::Msg>) -> bool {
-        self.value = !self.value;
+        self.value = self.value.next();
         true
     }
 
@@ -34,24 +145,85 @@ impl Field for ToggleField {
         self.value
     }
 
+    fn set_value(&mut self, value: Self::Value) {
+        self.value = value;
+    }
+
+    fn state(&self) -> Self::State {
+        self.value
+    }
+
+    fn set_state(&mut self, state: Self::State) {
+        self.value = state;
+    }
+
     fn has_changed(&self) -> bool {
         self.value != self.initial
     }
 
-    fn view(&self, _: bool) -> Node<Self::Msg> {
+    fn view(&self, disabled: bool) -> Node<Self::Msg> {
+        stylesheet::inject("toggle-css", CSS);
+
+        let placement = match self.placement {
+            Placement::Left => Some("is-reversed"),
+            Placement::Right => None,
+            Placement::Top => Some("is-stacked"),
+        };
+
+        let state = input![
+            C!["toggle-state"],
+            attrs! {At::Type => "checkbox", At::Checked => self.value.checked().as_at_value()},
+            IF!(disabled => attrs! {At::Disabled => ""}),
+            input_ev(Ev::Input, |_| ())
+        ];
+
+        let control = match self.checkbox {
+            true => self.view_checkbox(state),
+            false => self.view_toggle(state),
+        };
+
         label![
-            C!["label"],
-            div![
-                C!["toggle"],
-                input![
-                    C!["toggle-state"],
-                    attrs!(At::Type => "checkbox"),
-                    input_ev(Ev::Input, |_| ())
-                ],
-                div![C!["toggle-inner"], div![C!["indicator"]]],
-                div![C!["active-bg"]],
-            ],
-            div![C!["label-text"], &self.label]
+            C!["toggle-label", placement, IF!(disabled => "is-disabled")],
+            control,
+            div![&self.label]
         ]
     }
+
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn display_value(&self) -> String {
+        text(self.value)
+    }
+
+    fn display_initial(&self) -> String {
+        text(self.initial)
+    }
+
+    fn query(&self) -> Option<String> {
+        match self.value.indeterminate() {
+            true => None,
+            false => Some(self.value.checked().to_string()),
+        }
+    }
+
+    fn set_query(&mut self, query: &str) {
+        if let Ok(checked) = query.parse() {
+            self.value = T::from_checked(checked);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constructs_outside_the_browser() {
+        let mut toggle = ToggleField::new("active", false);
+        toggle.set_value(true);
+        assert!(toggle.has_changed());
+        assert_eq!(toggle.display_value(), "Yes");
+    }
 }
@@ -0,0 +1,251 @@
+use seed::prelude::*;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+use crate::builder::AnyField;
+use crate::color::ColorField;
+use crate::combinators::{ListField, MappedField};
+use crate::daterange::DateRangeField;
+use crate::datetime::{PickerField, Temporal};
+use crate::duration::DurationField;
+use crate::field::Field;
+use crate::file::FileField;
+use crate::form::{Form, FormViewOptions};
+use crate::input::InputField;
+use crate::map::MapField;
+use crate::money::MoneyField;
+use crate::password::PasswordField;
+use crate::range::RangeSliderField;
+use crate::select::SelectField;
+use crate::slider::SliderField;
+use crate::summary::{view_summary, view_summary_row};
+use crate::textarea::TextAreaField;
+use crate::toggle::{ToggleField, ToggleState};
+
+/// Field values which can be missing, e.g. an empty or invalid input.
+pub trait Required {
+    type Inner;
+
+    fn required(self) -> Option<Self::Inner>;
+}
+
+impl<T> Required for Option<T> {
+    type Inner = T;
+
+    fn required(self) -> Option<T> {
+        self
+    }
+}
+
+macro_rules! always_present {
+    ($($ty:ty),+) => {$(
+        impl Required for $ty {
+            type Inner = Self;
+
+            fn required(self) -> Option<Self> {
+                Some(self)
+            }
+        }
+    )+};
+}
+
+always_present!(bool, f64, i64, String);
+
+impl<T> Required for Vec<T> {
+    type Inner = Self;
+
+    fn required(self) -> Option<Self> {
+        Some(self)
+    }
+}
+
+impl<K, V> Required for BTreeMap<K, V> {
+    type Inner = Self;
+
+    fn required(self) -> Option<Self> {
+        Some(self)
+    }
+}
+
+impl<A: Required, B: Required> Required for (A, B) {
+    type Inner = (A::Inner, B::Inner);
+
+    fn required(self) -> Option<Self::Inner> {
+        Some((self.0.required()?, self.1.required()?))
+    }
+}
+
+/// Fields which tell whether their value is missing, a tuple form is only valid once none is.
+///
+/// This is decided per field rather than per value, as some fields use `None` as a proper value.
+pub trait RequiredField: Field {
+    type Inner;
+
+    fn required_value(&self) -> Option<Self::Inner>;
+}
+
+/// Fields whose value is missing exactly when `Required` says so.
+macro_rules! required_by_value {
+    ($([$($generics:tt)*] $field:ty),+ $(,)?) => {$(
+        impl<$($generics)*> RequiredField for $field {
+            type Inner = <<Self as Field>::Value as Required>::Inner;
+
+            fn required_value(&self) -> Option<Self::Inner> {
+                self.value().required()
+            }
+        }
+    )+};
+}
+
+required_by_value!(
+    [] AnyField,
+    [] ColorField,
+    [] DateRangeField,
+    [] DurationField,
+    [] FileField,
+    [T: ToString + Clone + PartialEq + Debug] InputField<T>,
+    [F: Field] ListField<F>,
+    [K: Ord + Clone + ToString, V: Clone + PartialEq + ToString] MapField<K, V>,
+    [F: Field, B] MappedField<F, B>,
+    [] MoneyField,
+    [] PasswordField,
+    [T: Temporal] PickerField<T>,
+    [] RangeSliderField,
+    [] SelectField,
+    [] SliderField,
+    [T: ToString + Clone + PartialEq + Debug] TextAreaField<T>,
+);
+
+// The unset state of a tri-state toggle is a value of its own
+impl<T: ToggleState> RequiredField for ToggleField<T> {
+    type Inner = T;
+
+    fn required_value(&self) -> Option<T> {
+        Some(self.value())
+    }
+}
+
+pub enum TupleMsg<A, B, C = (), D = (), E = (), F = ()> {
+    Field0(A),
+    Field1(B),
+    Field2(C),
+    Field3(D),
+    Field4(E),
+    Field5(F),
+}
+
+/// Forms made of a tuple of fields, for dialogs where deriving a struct is overkill.
+macro_rules! tuple_form {
+    ($($field:ident $index:tt $variant:ident),+) => {
+        impl<$($field: RequiredField),+> Form for ($($field,)+) {
+            type Msg = TupleMsg<$($field::Msg),+>;
+            type Value = Option<($(<$field as RequiredField>::Inner,)+)>;
+            type Snapshot = ($($field::State,)+);
+            type Patch = ($(Option<$field::Value>,)+);
+
+            #[allow(unreachable_patterns)]
+            fn update(&mut self, msg: Self::Msg, orders: &mut impl Orders<Self::Msg>) -> bool {
+                match msg {
+                    $(TupleMsg::$variant(msg) => {
+                        self.$index.update(msg, &mut orders.proxy(TupleMsg::$variant))
+                    })+
+                    _ => false,
+                }
+            }
+
+            fn reset(&mut self) {
+                $(self.$index.reset();)+
+            }
+
+            fn value(&self) -> Self::Value {
+                Some(($(self.$index.required_value()?,)+))
+            }
+
+            fn snapshot(&self) -> Self::Snapshot {
+                ($(self.$index.state(),)+)
+            }
+
+            fn restore(&mut self, snapshot: Self::Snapshot) {
+                $(self.$index.set_state(snapshot.$index);)+
+            }
+
+            fn changes(&self) -> Self::Patch {
+                ($(self.$index.has_changed().then(|| self.$index.value()),)+)
+            }
+
+            fn view_with(&self, options: FormViewOptions) -> Vec<Node<Self::Msg>> {
+                vec![$(self.$index.view_with(options).map_msg(TupleMsg::$variant)),+]
+            }
+
+            fn view_summary(&self) -> Node<Self::Msg> {
+                view_summary(vec![$(view_summary_row(&self.$index)),+])
+            }
+
+            fn names(&self) -> Vec<String> {
+                vec![$(stringify!($index).to_owned()),+]
+            }
+
+            fn query(&self) -> Vec<(String, String)> {
+                let mut query = Vec::new();
+                $(if let Some(value) = self.$index.query() {
+                    query.push((stringify!($index).to_owned(), value));
+                })+
+                query
+            }
+
+            fn restore_query(&mut self, search: &UrlSearch) {
+                $(if let Some(query) = search.get(stringify!($index)).and_then(|values| values.first()) {
+                    self.$index.set_query(query);
+                })+
+            }
+
+            fn has_changed(&self) -> bool {
+                false $(|| self.$index.has_changed())+
+            }
+        }
+    };
+}
+
+tuple_form!(A 0 Field0, B 1 Field1);
+tuple_form!(A 0 Field0, B 1 Field1, C 2 Field2);
+tuple_form!(A 0 Field0, B 1 Field1, C 2 Field2, D 3 Field3);
+tuple_form!(A 0 Field0, B 1 Field1, C 2 Field2, D 3 Field3, E 4 Field4);
+tuple_form!(A 0 Field0, B 1 Field1, C 2 Field2, D 3 Field3, E 4 Field4, F 5 Field5);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combinators::OptionalField;
+    use crate::controller::FormController;
+
+    #[test]
+    fn pairs_are_forms() {
+        let mut form = (ToggleField::new("a", false), ToggleField::new("b", true));
+        assert_eq!(form.value(), Some((false, true)));
+
+        form.restore((true, true));
+        assert_eq!(form.snapshot(), (true, true));
+        assert_eq!(form.value(), Some((true, true)));
+        assert_eq!(form.changes(), (Some(true), None));
+        assert!(form.has_changed());
+    }
+
+    #[test]
+    fn unset_toggles_are_present() {
+        let form = (ToggleField::new("a", None), ToggleField::new("b", true));
+        assert_eq!(form.value(), Some((None, true)));
+
+        let mut controller = FormController::new(form);
+        assert_eq!(controller.submit(), Some((None, true)));
+    }
+
+    #[test]
+    fn disabled_optional_fields_are_present() {
+        let optional = || OptionalField::new("a", ToggleField::new("a", false));
+        let mut form = (optional(), ToggleField::new("b", true));
+        assert_eq!(form.value(), Some((None, true)));
+
+        form.restore(((true, false), true));
+        assert_eq!(form.value(), Some((Some(false), true)));
+    }
+}
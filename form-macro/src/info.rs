@@ -1,9 +1,125 @@
-use proc_macro2::Ident;
-use syn::Type;
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::{GenericArgument, PathArguments, Type};
+
+pub enum Kind {
+    Input,
+    Toggle,
+    Slider,
+    RangeSlider,
+    File,
+}
 
 pub struct FieldInfo {
     pub name: Ident,
     pub msg_name: Ident,
     pub ty: Type,
-    pub initial: Option<String>,
+    pub kind: Kind,
+}
+
+impl FieldInfo {
+    pub fn field_ty(&self) -> TokenStream {
+        let ty = &self.ty;
+        match self.kind {
+            Kind::Input => quote! { frontend::InputField<#ty> },
+            Kind::Toggle => quote! { frontend::ToggleField<#ty> },
+            Kind::Slider => quote! { frontend::SliderField },
+            Kind::RangeSlider => quote! { frontend::RangeSliderField },
+            Kind::File => quote! { frontend::FileField },
+        }
+    }
+
+    pub fn constructor(&self) -> TokenStream {
+        let ty = &self.ty;
+        let label = self.name.to_string();
+        match self.kind {
+            Kind::Input => quote! {
+                frontend::InputField::new(#label, |x| {
+                    x.parse::<#ty>().map_err(|_| "Invalid value.".to_owned())
+                })
+            },
+            Kind::Toggle => quote! { frontend::ToggleField::new(#label, Default::default()) },
+            Kind::Slider => quote! { frontend::SliderField::linear(#label, 0.) },
+            Kind::RangeSlider => quote! { frontend::RangeSliderField::linear(#label, (0., 1.)) },
+            Kind::File if self.is_file_list() => {
+                quote! { frontend::FileField::new(#label).multiple() }
+            }
+            Kind::File => quote! { frontend::FileField::new(#label) },
+        }
+    }
+
+    /// Converts the value of the field, bound to its name, into the type of the struct field.
+    /// Fields with an invalid value make the whole form value, or its changes, `None`.
+    pub fn extract(&self) -> TokenStream {
+        let name = &self.name;
+        match self.kind {
+            Kind::Input => quote! { #name? },
+            Kind::Toggle | Kind::Slider | Kind::RangeSlider => quote! { #name },
+            Kind::File if self.is_file_list() => quote! { #name },
+            Kind::File if is_uploaded_file(&self.ty) => {
+                quote! { #name.into_iter().next()? }
+            }
+            Kind::File => quote! { #name.into_iter().next()?.bytes },
+        }
+    }
+
+    /// Whether the field is a `Vec<UploadedFile>`, as opposed to a single file or its bytes.
+    fn is_file_list(&self) -> bool {
+        let segment = match &self.ty {
+            Type::Path(ty) => ty.path.segments.last(),
+            _ => None,
+        };
+        let args = match segment {
+            Some(segment) if segment.ident == "Vec" => &segment.arguments,
+            _ => return false,
+        };
+        match args {
+            PathArguments::AngleBracketed(args) => args.args.iter().any(|arg| match arg {
+                GenericArgument::Type(ty) => is_uploaded_file(ty),
+                _ => false,
+            }),
+            _ => false,
+        }
+    }
+}
+
+/// Matches `UploadedFile` by the last segment of its path, so `frontend::UploadedFile` works too.
+fn is_uploaded_file(ty: &Type) -> bool {
+    match ty {
+        Type::Path(ty) => {
+            ty.path.segments.last().is_some_and(|segment| {
+                segment.ident == "UploadedFile" && segment.arguments.is_empty()
+            })
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proc_macro2::Span;
+
+    fn file(ty: &str) -> FieldInfo {
+        FieldInfo {
+            name: Ident::new("upload", Span::call_site()),
+            msg_name: Ident::new("Upload", Span::call_site()),
+            ty: syn::parse_str(ty).unwrap(),
+            kind: Kind::File,
+        }
+    }
+
+    #[test]
+    fn file_lists() {
+        assert!(file("Vec<UploadedFile>").is_file_list());
+        assert!(file("std::vec::Vec<frontend::UploadedFile>").is_file_list());
+        assert!(!file("Option<UploadedFile>").is_file_list());
+        assert!(!file("Vec<u8>").is_file_list());
+    }
+
+    #[test]
+    fn single_files() {
+        assert!(is_uploaded_file(&file("frontend::UploadedFile").ty));
+        assert!(!is_uploaded_file(&file("Option<UploadedFile>").ty));
+    }
 }
@@ -1,16 +1,16 @@
 use proc_macro2::{Ident, TokenStream};
 use quote::quote;
-use syn::{parse_macro_input, DeriveInput, Field};
+use syn::{parse_macro_input, Attribute, DeriveInput, Field, Meta, NestedMeta};
 
-use crate::info::FieldInfo;
+use crate::info::{FieldInfo, Kind};
 
 mod info;
 
 fn msg_enum(fields: &[FieldInfo]) -> TokenStream {
     let mapper = |field: &FieldInfo| {
         let msg_name = &field.msg_name;
-        let ty = &field.ty;
-        quote! { #msg_name(<frontend::InputField<#ty> as frontend::Field>::Msg) }
+        let field_ty = field.field_ty();
+        quote! { #msg_name(<#field_ty as frontend::Field>::Msg) }
     };
 
     let defs = fields.iter().map(mapper);
@@ -20,7 +20,8 @@ fn msg_enum(fields: &[FieldInfo]) -> TokenStream {
 fn form_struct(fields: &[FieldInfo], form_name: &Ident) -> TokenStream {
     let mapper = |field: &FieldInfo| {
         let name = &field.name;
-        quote! { #name: frontend::InputField<String> }
+        let field_ty = field.field_ty();
+        quote! { #name: #field_ty }
     };
 
     let defs = fields.iter().map(mapper);
@@ -34,7 +35,8 @@ fn form_fn(form_name: &Ident) -> TokenStream {
 fn new_fn(fields: &[FieldInfo], form_name: &Ident) -> TokenStream {
     let mapper = |field: &FieldInfo| {
         let name = &field.name;
-        quote! {#name: frontend::InputField::string(stringify!(name)) }
+        let constructor = field.constructor();
+        quote! {#name: #constructor }
     };
 
     let defs = fields.iter().map(mapper);
@@ -85,7 +87,8 @@ fn value_fn(fields: &[FieldInfo], name: &Ident) -> TokenStream {
 
     let mapper2 = |field: &FieldInfo| {
         let name = &field.name;
-        quote! { #name: #name? }
+        let extract = field.extract();
+        quote! { #name: #extract }
     };
 
     let defs = fields.iter().map(mapper);
@@ -99,22 +102,153 @@ fn value_fn(fields: &[FieldInfo], name: &Ident) -> TokenStream {
     }
 }
 
+fn snapshot_fns(fields: &[FieldInfo]) -> TokenStream {
+    let names: Vec<_> = fields.iter().map(|field| &field.name).collect();
+
+    quote! {
+        fn snapshot(&self) -> Self::Snapshot {
+            (#(self.#names.state(),)*)
+        }
+
+        fn restore(&mut self, snapshot: Self::Snapshot) {
+            let (#(#names,)*) = snapshot;
+            #(self.#names.set_state(#names);)*
+        }
+    }
+}
+
+/// The patch only derives `Serialize` with `#[serialize_patch]`, as all field types must support it.
+fn patch_struct(fields: &[FieldInfo], patch_name: &Ident, serialize: bool) -> TokenStream {
+    let mapper = |field: &FieldInfo| {
+        let name = &field.name;
+        let ty = &field.ty;
+        let skip = if serialize {
+            quote! { #[serde(skip_serializing_if = "std::option::Option::is_none")] }
+        } else {
+            quote! {}
+        };
+        quote! {
+            #skip
+            pub #name: std::option::Option<#ty>
+        }
+    };
+
+    let defs = fields.iter().map(mapper);
+    let derive = if serialize {
+        quote! {
+            #[derive(frontend::serde::Serialize)]
+            #[serde(crate = "frontend::serde")]
+        }
+    } else {
+        quote! {}
+    };
+    quote! {
+        #[derive(Debug, Clone, Default, PartialEq)]
+        #derive
+        struct #patch_name { #(#defs),* }
+    }
+}
+
+fn changes_fn(fields: &[FieldInfo], patch_name: &Ident) -> TokenStream {
+    let mapper = |field: &FieldInfo| {
+        let name = &field.name;
+        let extract = field.extract();
+        quote! {
+            #name: match self.#name.has_changed() {
+                true => {
+                    let #name = self.#name.value();
+                    Some(#extract)
+                }
+                false => None,
+            }
+        }
+    };
+
+    let defs = fields.iter().map(mapper);
+
+    quote! {
+        /// `None` while any of the changed fields is invalid.
+        fn changes(&self) -> Self::Patch {
+            Some(#patch_name { #(#defs),* })
+        }
+    }
+}
+
+fn apply_patch_fn(fields: &[FieldInfo], patch_name: &Ident) -> TokenStream {
+    let names = fields.iter().map(|field| &field.name);
+
+    quote! {
+        /// Overwrites the fields which are present in the patch.
+        pub fn apply_patch(&mut self, patch: #patch_name) {
+            #(if let Some(value) = patch.#names {
+                self.#names = value;
+            })*
+        }
+    }
+}
+
 fn view_fn(fields: &[FieldInfo]) -> TokenStream {
     let mapper = |field: &FieldInfo| {
         let name = &field.name;
         let msg_name = &field.msg_name;
-        quote! {self. #name .view(false).map_msg(Msg::#msg_name) }
+        quote! {self. #name .view_with(options).map_msg(Msg::#msg_name) }
     };
 
     let defs = fields.iter().map(mapper);
 
     quote! {
-        fn view(&self) -> Vec<Node<Msg>> {
+        fn view_with(&self, options: frontend::FormViewOptions) -> Vec<Node<Msg>> {
             vec![#(#defs),*]
         }
     }
 }
 
+fn view_summary_fn(fields: &[FieldInfo]) -> TokenStream {
+    let mapper = |field: &FieldInfo| {
+        let name = &field.name;
+        quote! { frontend::view_summary_row(&self.#name) }
+    };
+
+    let defs = fields.iter().map(mapper);
+
+    quote! {
+        fn view_summary(&self) -> Node<Msg> {
+            frontend::view_summary(vec![#(#defs),*])
+        }
+    }
+}
+
+fn names_fn(fields: &[FieldInfo]) -> TokenStream {
+    let names = fields.iter().map(|field| field.name.to_string());
+
+    quote! {
+        fn names(&self) -> Vec<String> {
+            vec![#(#names.to_owned()),*]
+        }
+    }
+}
+
+fn query_fns(fields: &[FieldInfo]) -> TokenStream {
+    let names: Vec<_> = fields.iter().map(|field| &field.name).collect();
+    let keys: Vec<_> = names.iter().map(|name| name.to_string()).collect();
+
+    quote! {
+        fn query(&self) -> Vec<(String, String)> {
+            let mut query = Vec::new();
+            #(if let Some(value) = self.#names.query() {
+                query.push((#keys.to_owned(), value));
+            })*
+            query
+        }
+
+        fn restore_query(&mut self, search: &seed::prelude::UrlSearch) {
+            #(if let Some(query) = search.get(#keys).and_then(|values| values.first()) {
+                self.#names.set_query(query);
+            })*
+        }
+    }
+}
+
 fn has_changed_fn(fields: &[FieldInfo]) -> TokenStream {
     let mapper = |field: &FieldInfo| {
         let name = &field.name;
@@ -130,19 +264,33 @@ fn has_changed_fn(fields: &[FieldInfo]) -> TokenStream {
     }
 }
 
-fn derive_form_trait(fields: &[FieldInfo], name: &Ident) -> proc_macro::TokenStream {
+fn derive_form_trait(
+    fields: &[FieldInfo],
+    name: &Ident,
+    serialize_patch: bool,
+) -> proc_macro::TokenStream {
     let form_name = Ident::new(&format!("{}Form", name), name.span());
+    let patch_name = Ident::new(&format!("{}Patch", name), name.span());
+
+    let field_tys = fields.iter().map(FieldInfo::field_ty);
 
     let msg_enum = msg_enum(fields);
     let form_struct = form_struct(fields, &form_name);
+    let patch_struct = patch_struct(fields, &patch_name, serialize_patch);
 
     let form_fn = form_fn(&form_name);
     let new_fn = new_fn(fields, &form_name);
+    let apply_patch_fn = apply_patch_fn(fields, &patch_name);
 
     let update_fn = update_fn(fields);
     let reset_fn = reset_fn(fields);
     let value_fn = value_fn(fields, name);
+    let snapshot_fns = snapshot_fns(fields);
+    let changes_fn = changes_fn(fields, &patch_name);
     let view_fn = view_fn(fields);
+    let view_summary_fn = view_summary_fn(fields);
+    let names_fn = names_fn(fields);
+    let query_fns = query_fns(fields);
     let has_changed_fn = has_changed_fn(fields);
 
     let expanded = quote! {
@@ -155,9 +303,11 @@ fn derive_form_trait(fields: &[FieldInfo], name: &Ident) -> proc_macro::TokenStr
 
         #msg_enum
         #form_struct
+        #patch_struct
 
         impl #name {
             #form_fn
+            #apply_patch_fn
         }
 
         impl #form_name {
@@ -167,18 +317,56 @@ fn derive_form_trait(fields: &[FieldInfo], name: &Ident) -> proc_macro::TokenStr
         impl Form for #form_name {
             type Msg = Msg;
             type Value = std::option::Option<#name>;
+            type Snapshot = (#(<#field_tys as frontend::Field>::State,)*);
+            type Patch = std::option::Option<#patch_name>;
 
             #update_fn
             #reset_fn
             #value_fn
+            #snapshot_fns
+            #changes_fn
             #view_fn
+            #view_summary_fn
+            #names_fn
+            #query_fns
             #has_changed_fn
         }
     };
     expanded.into()
 }
 
-#[proc_macro_derive(Form, attributes(input, autocomplete, toggle, slider))]
+fn kind(attrs: &[Attribute]) -> Kind {
+    let has_flag = |meta: &Meta, flag: &str| match meta {
+        Meta::List(list) => list.nested.iter().any(|nested| match nested {
+            NestedMeta::Meta(meta) => meta.path().is_ident(flag),
+            _ => false,
+        }),
+        _ => false,
+    };
+
+    for attr in attrs {
+        let meta = match attr.parse_meta() {
+            Ok(meta) => meta,
+            Err(_) => continue,
+        };
+
+        if meta.path().is_ident("toggle") {
+            return Kind::Toggle;
+        } else if meta.path().is_ident("file") {
+            return Kind::File;
+        } else if meta.path().is_ident("slider") && has_flag(&meta, "range") {
+            return Kind::RangeSlider;
+        } else if meta.path().is_ident("slider") {
+            return Kind::Slider;
+        }
+    }
+    Kind::Input
+}
+
+#[proc_macro_derive(
+    Form,
+    attributes(input, autocomplete, toggle, slider, file, serialize_patch)
+)]
 pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
     let name = &ast.ident;
@@ -208,13 +396,17 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
             name,
             msg_name,
             ty: field.ty.clone(),
-            initial: None,
+            kind: kind(&field.attrs),
         }
     };
 
     let fields: Vec<_> = raw.iter().map(mapper).collect();
+    let serialize_patch = ast
+        .attrs
+        .iter()
+        .any(|attr| attr.path.is_ident("serialize_patch"));
 
-    let result = derive_form_trait(&fields, name);
+    let result = derive_form_trait(&fields, name, serialize_patch);
     // panic!("{:?}", result.to_string());
     result
 }
@@ -0,0 +1,44 @@
+use frontend::Form;
+
+#[derive(Debug, PartialEq, Form)]
+#[serialize_patch]
+struct Ingredient {
+    name: String,
+    grams: i64,
+    #[toggle]
+    vegan: bool,
+}
+
+#[test]
+fn patches_only_contain_changed_fields() {
+    let mut form = Ingredient::form();
+    assert_eq!(form.changes(), Some(IngredientPatch::default()));
+
+    form.grams.set_value(Some(250));
+    form.vegan.set_value(true);
+    let patch = form.changes().unwrap();
+    assert_eq!(
+        serde_json::to_string(&patch).unwrap(),
+        r#"{"grams":250,"vegan":true}"#
+    );
+
+    let mut ingredient = Ingredient {
+        name: "flour".to_owned(),
+        grams: 100,
+        vegan: false,
+    };
+    ingredient.apply_patch(patch);
+    assert_eq!(ingredient.grams, 250);
+    assert_eq!(ingredient.name, "flour");
+    assert!(ingredient.vegan);
+}
+
+#[test]
+fn invalid_changes_give_no_patch() {
+    let mut form = Ingredient::form();
+    form.grams.set_state("lots".to_owned());
+    assert_eq!(form.changes(), None);
+
+    form.grams.set_state(String::new());
+    assert_eq!(form.changes(), Some(IngredientPatch::default()));
+}
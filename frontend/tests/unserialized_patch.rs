@@ -0,0 +1,41 @@
+use frontend::Form;
+use std::fmt;
+use std::str::FromStr;
+
+/// A `FromStr` type without serde support, which only works with unserialized patches.
+#[derive(Debug, Clone, PartialEq)]
+struct Shelf(u8);
+
+impl FromStr for Shelf {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        s.strip_prefix('S')
+            .and_then(|x| x.parse().ok())
+            .map(Shelf)
+            .ok_or(())
+    }
+}
+
+impl fmt::Display for Shelf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "S{}", self.0)
+    }
+}
+
+#[derive(Debug, PartialEq, Form)]
+struct Storage {
+    shelf: Shelf,
+}
+
+#[test]
+fn patches_need_no_serde_by_default() {
+    let mut form = Storage::form();
+    form.shelf.set_state("S4".to_owned());
+    let patch = form.changes().unwrap();
+    assert_eq!(patch.shelf, Some(Shelf(4)));
+
+    let mut storage = Storage { shelf: Shelf(1) };
+    storage.apply_patch(patch);
+    assert_eq!(storage.shelf, Shelf(4));
+}
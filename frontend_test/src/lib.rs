@@ -3,6 +3,7 @@ use seed::{prelude::*, *};
 use frontend::Form;
 
 #[derive(Form)]
+#[allow(dead_code)]
 struct Ingredient {
     name: String,
     category: String,
@@ -16,16 +17,18 @@ enum M {
     Form(<IngredientForm as Form>::Msg),
 }
 
-fn init(_: Url, orders: &mut impl Orders<M>) -> Model {
-    Model {
-        form: Ingredient::form(),
-    }
+fn init(url: Url, _: &mut impl Orders<M>) -> Model {
+    let mut form = Ingredient::form();
+    form.restore_query(url.search());
+    Model { form }
 }
 
 fn update(msg: M, model: &mut Model, orders: &mut impl Orders<M>) {
     match msg {
         M::Form(msg) => {
-            model.form.update(msg, &mut orders.proxy(M::Form));
+            if model.form.update(msg, &mut orders.proxy(M::Form)) {
+                frontend::replace_query(&model.form);
+            }
         }
     };
 }